@@ -0,0 +1,56 @@
+#pragma once
+#include <vector>
+#include <iostream>
+#include <cstdlib>
+
+namespace array {
+    template <typename T>
+    int len(const std::vector<T>& array) {
+        return (int) array.size();
+    }
+
+    // The element type is only deduced from the array, so `push(a, {})` works.
+    template <typename T>
+    void push(std::vector<T>& array, const typename std::vector<T>::value_type& value) {
+        array.push_back(value);
+    }
+
+    template <typename T>
+    T pop(std::vector<T>& array) {
+        if (array.empty()) {
+            std::cerr << "Runtime error: Cannot pop from an empty array" << std::endl;
+            std::abort();
+        }
+
+        T value = array.back();
+        array.pop_back();
+        return value;
+    }
+
+    // Used for every `a[i]` in generated code. Debug builds check the bounds and report the
+    // location in the Pulse source.
+    template <typename V>
+    auto& at(V&& array, int index, const char* location) {
+#ifndef NDEBUG
+        if (index < 0 || index >= (int) array.size()) {
+            std::cerr << "Runtime error: Index out of bounds: the length is " << array.size()
+                      << " but the index is " << index << " at " << location << std::endl;
+            std::abort();
+        }
+#endif
+        return array[index];
+    }
+}
+
+// Printing an array matches the interpreter, `[1, 2, 3]`.
+template <typename T>
+std::ostream& operator<<(std::ostream& out, const std::vector<T>& array) {
+    out << std::boolalpha << "[";
+    for (size_t i = 0; i < array.size(); i++) {
+        if (i != 0) {
+            out << ", ";
+        }
+        out << array[i];
+    }
+    return out << "]";
+}
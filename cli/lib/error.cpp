@@ -1,8 +1,7 @@
+#pragma once
 #include <string>
 #include <utility>
 
-using namespace std;
-
 struct Error : public std::exception {
     std::string kind;
     std::string message;
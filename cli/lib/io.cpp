@@ -1,45 +1,40 @@
+#pragma once
 #include <iostream>
-using namespace std;
-
 
 namespace io {
-    void println() {
-        std::cout << std::endl;
-    }
-
-    template <typename T, typename... Args>
-    void println(T first, Args... args) {
-        std::cout << first << " " << std::boolalpha;
-        println(args...);
-    }
-
-    void eprintln() {
-        std::cerr << std::endl;
+    // Arguments are separated by one space, like the interpreter prints them.
+    void write(std::ostream& out) {
     }
 
     template <typename T, typename... Args>
-    void eprintln(T first, Args... args) {
-        std::cerr << first << " " << std::boolalpha;
-        eprintln(args...);
+    void write(std::ostream& out, T first, Args... args) {
+        out << std::boolalpha << first;
+        if (sizeof...(args) > 0) {
+            out << " ";
+        }
+        write(out, args...);
     }
 
-    void print() {
-        std::cout;
+    template <typename... Args>
+    void println(Args... args) {
+        write(std::cout, args...);
+        std::cout << std::endl;
     }
 
-    template <typename T, typename... Args>
-    void print(T first, Args... args) {
-        std::cout << first << " " << std::boolalpha;
-        print(args...);
+    template <typename... Args>
+    void eprintln(Args... args) {
+        write(std::cerr, args...);
+        std::cerr << std::endl;
     }
 
-    void eprint() {
-        std::cerr;
+    template <typename... Args>
+    void print(Args... args) {
+        write(std::cout, args...);
+        std::cout << std::flush;
     }
 
-    template <typename T, typename... Args>
-    void eprint(T first, Args... args) {
-        std::cerr << first << " " << std::boolalpha;
-        eprint(args...);
+    template <typename... Args>
+    void eprint(Args... args) {
+        write(std::cerr, args...);
     }
 }
@@ -1,3 +1,6 @@
+#pragma once
+// Declares `operator<<` for arrays, it has to be visible before the io templates use it.
+#include "./array.cpp"
 #include "./io.cpp"
 #include "./math.cpp"
 #include "./env.cpp"
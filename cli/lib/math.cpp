@@ -1,7 +1,76 @@
+#pragma once
 #include <cmath>
+#include <climits>
+#include <cstdlib>
+#include <iostream>
 
 namespace math {
     double sqrt(double x) {
         return std::sqrt(x);
     }
-}
\ No newline at end of file
+
+    // Integer exponentiation used for `**`. Overflow wraps around like the other integer
+    // operators, and a negative exponent truncates toward zero like integer division, so
+    // only 1 and -1 give a non-zero result.
+    int ipow(int base, int exponent) {
+        if (exponent < 0) {
+            if (base == 1) {
+                return 1;
+            }
+            if (base == -1) {
+                return exponent % 2 == 0 ? 1 : -1;
+            }
+            return 0;
+        }
+
+        unsigned int result = 1;
+        unsigned int factor = (unsigned int) base;
+        while (exponent > 0) {
+            if (exponent & 1) {
+                result *= factor;
+            }
+            factor *= factor;
+            exponent >>= 1;
+        }
+
+        return (int) result;
+    }
+
+    // Integer division and remainder used for `/` and `%`. Dividing by zero is a runtime error
+    // like in the interpreter, and `INT_MIN / -1` wraps around like the other operators.
+    void check_divisor(int divisor, const char* location) {
+        if (divisor == 0) {
+            std::cerr << "Runtime error: Attempted to divide by zero at " << location << std::endl;
+            std::abort();
+        }
+    }
+
+    int idiv(int left, int right, const char* location) {
+        check_divisor(right, location);
+        if (right == -1) {
+            return (int) (0u - (unsigned int) left);
+        }
+
+        return left / right;
+    }
+
+    int irem(int left, int right, const char* location) {
+        check_divisor(right, location);
+        if (right == -1) {
+            return 0;
+        }
+
+        return left % right;
+    }
+
+    // Steps a `for` loop counter by `step`, which is positive. Returns false instead when the
+    // counter would pass the largest int, which ends the loop like in the interpreter.
+    bool advance(int& counter, int step) {
+        if (counter > INT_MAX - step) {
+            return false;
+        }
+
+        counter += step;
+        return true;
+    }
+}
@@ -0,0 +1,14 @@
+use colored::Colorize;
+use pulse_core::error::error::Error;
+use pulse_core::error::explanations::explain;
+use pulse_core::Result;
+
+pub fn explain_command(code: String) -> Result<()> {
+    let code = code.to_uppercase();
+    let explanation = explain(&code).ok_or_else(|| Error::UnknownErrorCode(code.clone()))?;
+
+    println!("{}\n", code.bright_cyan());
+    println!("{}", explanation);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use colored::Colorize;
+use pulse_core::error::error::Error;
+use pulse_core::repl::Repl;
+use pulse_core::Result;
+use std::io::{stderr, stdin, stdout, BufWriter, Write};
+
+pub fn repl_command() -> Result<()> {
+    let mut repl = Repl::new();
+    let mut stderr = BufWriter::new(stderr());
+
+    println!(
+        "{} {} {}",
+        "Pulse".bright_cyan(),
+        env!("CARGO_PKG_VERSION"),
+        "(press Ctrl+D or type :exit to quit)".dimmed()
+    );
+
+    while let Some(input) = read_entry()? {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed == ":exit" || trimmed == ":quit" {
+            break;
+        }
+
+        match repl.eval(&input) {
+            Ok(Some((value, ty))) => println!("{}{}", value.repr(), format!(": {}", ty).dimmed()),
+            Ok(None) => {}
+            Err(err) => {
+                err.log_pretty(&mut stderr);
+                stderr.flush().map_err(Error::io)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads lines until every opened brace is closed, so functions can span multiple lines.
+fn read_entry() -> Result<Option<String>> {
+    let mut input = String::new();
+    let mut depth = 0i32;
+
+    loop {
+        let prompt = if input.is_empty() { ">> " } else { ".. " };
+        print!("{}", prompt.bright_cyan());
+        stdout().flush().map_err(Error::io)?;
+
+        let mut line = String::new();
+        if stdin().read_line(&mut line).map_err(Error::io)? == 0 {
+            return Ok(if input.is_empty() { None } else { Some(input) });
+        }
+
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        input.push_str(line.trim_end_matches(['\r', '\n']));
+
+        if depth <= 0 {
+            return Ok(Some(input));
+        }
+        input.push('\n');
+    }
+}
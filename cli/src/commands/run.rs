@@ -7,6 +7,7 @@ use log::debug;
 use pulse_core::build::BuildProcess;
 use pulse_core::error::error::Error;
 use pulse_core::Result;
+use std::io::{stderr, BufWriter, Write};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use std::{env, fs};
@@ -53,6 +54,7 @@ pub fn setup_build_dir() -> Result<()> {
     let build_dir = build_dir()?;
 
     fs::create_dir_all(build_dir.join("std")).map_err(Error::io)?;
+    fs::create_dir_all(build_dir.join("source")).map_err(Error::io)?;
 
     // Include and write files
     let files = include_files!(
@@ -60,9 +62,17 @@ pub fn setup_build_dir() -> Result<()> {
         "../../lib/io.cpp",
         "../../lib/math.cpp",
         "../../lib/env.cpp",
-        "../../lib/error.cpp"
+        "../../lib/error.cpp",
+        "../../lib/array.cpp"
     );
-    let names = vec!["lib.cpp", "io.cpp", "math.cpp", "env.cpp", "error.cpp"];
+    let names = vec![
+        "lib.cpp",
+        "io.cpp",
+        "math.cpp",
+        "env.cpp",
+        "error.cpp",
+        "array.cpp",
+    ];
 
     for (name, content) in names.into_iter().zip(files) {
         let file_path = build_dir.join("std").join(name);
@@ -72,13 +82,26 @@ pub fn setup_build_dir() -> Result<()> {
     Ok(())
 }
 
-pub fn run_command(path: PathBuf) -> Result<()> {
+/// Runs the file and returns the exit status of the program.
+pub fn run_command(
+    path: PathBuf,
+    interpret: bool,
+    deny_warnings: bool,
+    args: Vec<String>,
+) -> Result<i32> {
     let (source, full_path) = resolve_file(path)?;
     debug!("Running command with source: {}", source);
+
+    if interpret {
+        return interpret_file(source, full_path, deny_warnings, args);
+    }
+
     setup_build_dir()?;
 
     let transpile_start = Instant::now();
     let mut build = BuildProcess::new(source, full_path.clone());
+    build.deny_warnings = deny_warnings;
+    analyze(&mut build)?;
     let code = build.compile()?;
 
     println!(
@@ -107,9 +130,10 @@ pub fn run_command(path: PathBuf) -> Result<()> {
             format_time(compile_start)
         );
 
+        // Named like `compile_cpp_file` names it, `.exe` is only added on Windows.
         let exe_path = build_dir()?.join(
             full_path
-                .with_extension("exe")
+                .with_extension(env::consts::EXE_EXTENSION)
                 .file_name()
                 .unwrap()
                 .to_str()
@@ -118,6 +142,7 @@ pub fn run_command(path: PathBuf) -> Result<()> {
         println!("        {} {}", "Running".bright_cyan(), exe_path.display());
 
         let output = std::process::Command::new(exe_path)
+            .args(args)
             .output()
             .map_err(Error::io)?;
         let to_display = String::from_utf8_lossy(&output.stdout);
@@ -130,12 +155,41 @@ pub fn run_command(path: PathBuf) -> Result<()> {
         for line in err_to_display.lines() {
             eprintln!("{}", line);
         }
+
+        // No status when the program was killed by a signal, e.g. an aborted bounds check.
+        Ok(output.status.code().unwrap_or(1))
     } else {
         debug!("No C++ compiler found.");
-        return Err(Error::CompilerNotFound(looked_for.to_string()));
+        Err(Error::CompilerNotFound(looked_for.to_string()))
     }
+}
 
-    Ok(())
+pub fn interpret_file(
+    source: String,
+    full_path: PathBuf,
+    deny_warnings: bool,
+    args: Vec<String>,
+) -> Result<i32> {
+    println!(
+        "   {} {}",
+        "Interpreting".bright_cyan(),
+        full_path.display()
+    );
+
+    let mut build = BuildProcess::new(source, full_path);
+    build.deny_warnings = deny_warnings;
+    analyze(&mut build)?;
+    build.interpret(args)
+}
+
+/// Checks the program and prints its warnings. They do not stop the build, so they are
+/// printed here, before the program runs.
+fn analyze(build: &mut BuildProcess) -> Result<()> {
+    let warnings = build.analyze()?;
+
+    let mut stderr = BufWriter::new(stderr());
+    warnings.log_pretty(&mut stderr);
+    stderr.flush().map_err(Error::io)
 }
 
 pub fn compile_cpp_file(
@@ -149,8 +203,10 @@ pub fn compile_cpp_file(
     debug!("Creating exe with name: {}", file_stem);
 
     let output = match compiler {
+        // `-fwrapv` makes signed overflow wrap around, like integers do in the interpreter.
         Compiler::ClangPlus | Compiler::Gcc => std::process::Command::new(compiler_path)
             .arg(file)
+            .arg("-fwrapv")
             .arg("-o")
             .arg(file_stem)
             .current_dir(out_dir)
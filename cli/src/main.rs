@@ -8,20 +8,23 @@ mod macros;
 mod panic_handler;
 mod time;
 
+use crate::commands::explain::explain_command;
 use crate::commands::init::init_command;
+use crate::commands::repl::repl_command;
 use crate::commands::run::run_command;
 use crate::logger::setup_logger;
 use clap::{
     builder::{styling, PossibleValuesParser, Styles, TypedValueParser},
     Args, Parser, Subcommand, ValueHint,
 };
-use pulse_core::error::error::Error::NotImplemented;
 use std::io;
 use std::io::{stderr, BufWriter, Write};
 use std::path::PathBuf;
 
 pub mod commands {
+    pub mod explain;
     pub mod init;
+    pub mod repl;
     pub mod run;
 }
 
@@ -41,10 +44,24 @@ struct Program {
 
 #[derive(Debug, Subcommand)]
 enum Commands {
-    #[command(about = "Run a pulse file")]
+    #[command(about = "Run a pulse file, or start a REPL when no file is given")]
     Run {
         #[arg(name = "FILE", value_hint = ValueHint::FilePath)]
         file: Option<PathBuf>,
+
+        #[arg(long, help = "Run the file with the interpreter instead of a C++ compiler")]
+        interpret: bool,
+
+        #[arg(long, help = "Treat warnings as errors")]
+        deny_warnings: bool,
+
+        #[arg(
+            name = "ARGS",
+            help = "Arguments passed to the program's main",
+            trailing_var_arg = true,
+            allow_hyphen_values = true
+        )]
+        args: Vec<String>,
     },
 
     #[command(about = "Initialize a new project")]
@@ -52,6 +69,12 @@ enum Commands {
         #[arg(name = "NAME")]
         name: Option<String>,
     },
+
+    #[command(about = "Explain an error code, like P0009")]
+    Explain {
+        #[arg(name = "CODE")]
+        code: String,
+    },
 }
 
 fn main() {
@@ -60,21 +83,31 @@ fn main() {
     let program = Program::parse();
     setup_logger(program.verbose);
 
+    // The exit status of the program that was run.
     let result = match &program.command {
-        Commands::Run { file } => {
+        Commands::Run {
+            file,
+            interpret,
+            deny_warnings,
+            args,
+        } => {
             if let Some(file) = file {
-                run_command(file.clone())
+                run_command(file.clone(), *interpret, *deny_warnings, args.clone())
             } else {
-                // TODO: repl
-                Err(NotImplemented("REPL".to_string()))
+                repl_command().map(|_| 0)
             }
         }
-        Commands::Init { name } => init_command(name.clone()),
+        Commands::Init { name } => init_command(name.clone()).map(|_| 0),
+        Commands::Explain { code } => explain_command(code.clone()).map(|_| 0),
     };
 
     match result {
-        Ok(_) => {
-            log::debug!("Program finished successfully");
+        Ok(status) => {
+            log::debug!("Program finished with status {}", status);
+            if status != 0 {
+                stderr.flush().expect("Final result error writing");
+                std::process::exit(status);
+            }
         }
         Err(err) => {
             err.log_pretty(&mut stderr);
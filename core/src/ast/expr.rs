@@ -40,6 +40,7 @@ pub struct AssignExpr {
 pub enum UnOpKind {
     Minus,
     BitwiseNot,
+    LogicalNot,
 }
 
 impl Display for UnOpKind {
@@ -47,6 +48,7 @@ impl Display for UnOpKind {
         match self {
             UnOpKind::Minus => write!(f, "-"),
             UnOpKind::BitwiseNot => write!(f, "~"),
+            UnOpKind::LogicalNot => write!(f, "!"),
         }
     }
 }
@@ -101,6 +103,9 @@ pub enum BinOpKind {
     LessThanOrEqual,
     GreaterThan,
     GreaterThanOrEqual,
+    // Logical
+    LogicalAnd,
+    LogicalOr,
 }
 
 impl Display for BinOpKind {
@@ -121,6 +126,8 @@ impl Display for BinOpKind {
             BinOpKind::LessThanOrEqual => write!(f, "<="),
             BinOpKind::GreaterThan => write!(f, ">"),
             BinOpKind::GreaterThanOrEqual => write!(f, ">="),
+            BinOpKind::LogicalAnd => write!(f, "&&"),
+            BinOpKind::LogicalOr => write!(f, "||"),
         }
     }
 }
@@ -153,12 +160,14 @@ impl BinOperator {
             BinOpKind::BitwiseAnd => 17,
             BinOpKind::BitwiseXor => 16,
             BinOpKind::BitwiseOr => 15,
-            BinOpKind::Equals => 30,
-            BinOpKind::NotEquals => 30,
-            BinOpKind::LessThan => 29,
-            BinOpKind::LessThanOrEqual => 29,
-            BinOpKind::GreaterThan => 29,
-            BinOpKind::GreaterThanOrEqual => 29,
+            BinOpKind::LessThan => 14,
+            BinOpKind::LessThanOrEqual => 14,
+            BinOpKind::GreaterThan => 14,
+            BinOpKind::GreaterThanOrEqual => 14,
+            BinOpKind::Equals => 13,
+            BinOpKind::NotEquals => 13,
+            BinOpKind::LogicalAnd => 12,
+            BinOpKind::LogicalOr => 11,
         }
     }
 
@@ -183,12 +192,160 @@ pub struct NumberExpr {
     pub token: Token,
 }
 
+#[derive(Debug, Clone)]
+pub struct FloatExpr {
+    pub number: f64,
+    pub token: Token,
+}
+
 #[derive(Debug, Clone)]
 pub struct StringExpr {
     pub string: String,
     pub token: Token,
 }
 
+#[derive(Debug, Clone)]
+pub struct StructLiteralExpr {
+    pub identifier: Token,
+    pub left_brace: Token,
+    pub fields: Vec<FieldInitializer>,
+    pub right_brace: Token,
+}
+
+impl StructLiteralExpr {
+    pub fn struct_name(&self) -> &str {
+        &self.identifier.span.literal
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldInitializer {
+    pub identifier: Token,
+    pub value: ID,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldAccessExpr {
+    pub object: ID,
+    pub dot: Token,
+    pub field: Token,
+}
+
+impl FieldAccessExpr {
+    pub fn field_name(&self) -> &str {
+        &self.field.span.literal
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrayLiteralExpr {
+    pub left_bracket: Token,
+    pub elements: Vec<ID>,
+    pub right_bracket: Token,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexExpr {
+    pub object: ID,
+    pub left_bracket: Token,
+    pub index: ID,
+    pub right_bracket: Token,
+}
+
+/// Assignment to a field or an array element, `target` is always a `FieldAccess` or `Index`
+/// expression.
+#[derive(Debug, Clone)]
+pub struct PlaceAssignExpr {
+    pub target: ID,
+    pub equals: Token,
+    pub expression: ID,
+}
+
+/// Construction of an enum value, `Shape::Circle(2)` or `Shape::Empty`.
+#[derive(Debug, Clone)]
+pub struct EnumVariantExpr {
+    pub enum_name: Token,
+    pub variant: Token,
+    pub arguments: Vec<ID>,
+    pub right_paren: Option<Token>,
+}
+
+impl EnumVariantExpr {
+    pub fn enum_name(&self) -> &str {
+        &self.enum_name.span.literal
+    }
+
+    pub fn variant_name(&self) -> &str {
+        &self.variant.span.literal
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchExpr {
+    pub match_keyword: Token,
+    pub scrutinee: ID,
+    pub arms: Vec<MatchArm>,
+    pub right_brace: Token,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub fat_arrow: Token,
+    pub body: ID,
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Wildcard(Token),
+    /// A number, string or boolean expression compared with `==`.
+    Literal(ID),
+    Variant(VariantPattern),
+}
+
+impl Pattern {
+    pub fn span(&self, ast: &Ast) -> TextSpan {
+        match self {
+            Pattern::Wildcard(token) => token.span.clone(),
+            Pattern::Literal(expr) => ast.query_expr(*expr).span(ast),
+            Pattern::Variant(variant) => {
+                let mut spans = vec![variant.enum_name.span.clone(), variant.variant.span.clone()];
+                if let Some(right_paren) = &variant.right_paren {
+                    spans.push(right_paren.span.clone());
+                }
+                TextSpan::combine(spans)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantPattern {
+    pub enum_name: Token,
+    pub variant: Token,
+    pub bindings: Vec<PatternBinding>,
+    pub right_paren: Option<Token>,
+}
+
+impl VariantPattern {
+    pub fn variant_name(&self) -> &str {
+        &self.variant.span.literal
+    }
+}
+
+/// Name bound to one payload value of a variant, `_` ignores the value.
+#[derive(Debug, Clone)]
+pub struct PatternBinding {
+    pub identifier: Token,
+    pub variable_id: ID,
+}
+
+impl PatternBinding {
+    pub fn is_wildcard(&self) -> bool {
+        self.identifier.span.literal == "_"
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParenthesizedExpr {
     pub left_paren: Token,
@@ -196,9 +353,18 @@ pub struct ParenthesizedExpr {
     pub right_paren: Token,
 }
 
+/// Explicit conversion between numeric types, `x as float`.
+#[derive(Debug, Clone)]
+pub struct CastExpr {
+    pub expression: ID,
+    pub as_keyword: Token,
+    pub type_name: Token,
+}
+
 #[derive(Debug, Clone)]
 pub enum ExprKind {
     Number(NumberExpr),
+    Float(FloatExpr),
     Binary(BinaryExpr),
     Unary(UnaryExpr),
     Parenthesized(ParenthesizedExpr),
@@ -210,7 +376,15 @@ pub enum ExprKind {
     Block(BlockExpr),
     Error(TextSpan),
     String(StringExpr),
-    ScopedIdentifier { path: Vec<Token> }
+    ScopedIdentifier { path: Vec<Token> },
+    StructLiteral(StructLiteralExpr),
+    FieldAccess(FieldAccessExpr),
+    PlaceAssignment(PlaceAssignExpr),
+    ArrayLiteral(ArrayLiteralExpr),
+    Index(IndexExpr),
+    EnumVariant(EnumVariantExpr),
+    Match(MatchExpr),
+    Cast(CastExpr),
 }
 
 impl ExprKind {
@@ -273,6 +447,7 @@ impl Expr {
     pub fn span(&self, ast: &Ast) -> TextSpan {
         match &self.kind {
             ExprKind::Number(number) => number.token.span.clone(),
+            ExprKind::Float(number) => number.token.span.clone(),
             ExprKind::Boolean(boolean) => boolean.token.span.clone(),
             ExprKind::String(string) => string.token.span.clone(),
             ExprKind::Binary(binary) => {
@@ -281,9 +456,66 @@ impl Expr {
                 let right = ast.query_expr(binary.right).span(ast);
                 TextSpan::combine(vec![left, operator, right])
             }
+            ExprKind::Unary(unary) => {
+                let operator = unary.operator.token.span.clone();
+                let operand = ast.query_expr(unary.operand).span(ast);
+                TextSpan::combine(vec![operator, operand])
+            }
+            ExprKind::Parenthesized(parenthesized) => TextSpan::combine(vec![
+                parenthesized.left_paren.span.clone(),
+                parenthesized.right_paren.span.clone(),
+            ]),
             ExprKind::Variable(var) => var.identifier.span.clone(),
             ExprKind::Call(call) => combine_call_expr_span(call),
             ExprKind::If(if_expr) => if_expr.if_keyword.span.clone(),
+            ExprKind::Block(block) => TextSpan::combine(vec![
+                block.left_brace.span.clone(),
+                block.right_brace.span.clone(),
+            ]),
+            ExprKind::Assignment(assign) => {
+                let value = ast.query_expr(assign.expression).span(ast);
+                TextSpan::combine(vec![assign.identifier.span.clone(), value])
+            }
+            ExprKind::ScopedIdentifier { path } => {
+                TextSpan::combine(path.iter().map(|token| token.span.clone()).collect())
+            }
+            ExprKind::StructLiteral(literal) => TextSpan::combine(vec![
+                literal.identifier.span.clone(),
+                literal.right_brace.span.clone(),
+            ]),
+            ExprKind::FieldAccess(access) => {
+                let object = ast.query_expr(access.object).span(ast);
+                TextSpan::combine(vec![object, access.field.span.clone()])
+            }
+            ExprKind::PlaceAssignment(assign) => {
+                let target = ast.query_expr(assign.target).span(ast);
+                let value = ast.query_expr(assign.expression).span(ast);
+                TextSpan::combine(vec![target, value])
+            }
+            ExprKind::ArrayLiteral(literal) => TextSpan::combine(vec![
+                literal.left_bracket.span.clone(),
+                literal.right_bracket.span.clone(),
+            ]),
+            ExprKind::Index(index) => {
+                let object = ast.query_expr(index.object).span(ast);
+                TextSpan::combine(vec![object, index.right_bracket.span.clone()])
+            }
+            ExprKind::Cast(cast) => {
+                let expression = ast.query_expr(cast.expression).span(ast);
+                TextSpan::combine(vec![expression, cast.type_name.span.clone()])
+            }
+            ExprKind::EnumVariant(variant) => {
+                let mut spans = vec![variant.enum_name.span.clone(), variant.variant.span.clone()];
+                if let Some(right_paren) = &variant.right_paren {
+                    spans.push(right_paren.span.clone());
+                }
+                TextSpan::combine(spans)
+            }
+            ExprKind::Match(match_expr) => TextSpan::combine(vec![
+                match_expr.match_keyword.span.clone(),
+                match_expr.right_brace.span.clone(),
+            ]),
+            ExprKind::Error(span) => span.clone(),
             _ => {
                 log::debug!("No span for {:#?}", self);
                 unreachable!()
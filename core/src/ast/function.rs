@@ -28,6 +28,8 @@ pub struct FunctionDeclaration {
     pub func_keyword: Token,
     pub identifier: Token,
     pub parameters: Vec<ID>,
+    /// Names of `parameters` as written, in the same order.
+    pub parameter_identifiers: Vec<Token>,
     pub body: Body,
     pub return_type: Option<FunctionType>,
     pub id: ID,
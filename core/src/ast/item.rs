@@ -1,10 +1,13 @@
 use crate::ast::function::FunctionDeclaration;
+use crate::ast::structure::{EnumDeclaration, StructDeclaration};
 use crate::ast::ID;
 
 #[derive(Debug, Clone)]
 pub enum ItemKind {
     Stmt(ID),
     Function(FunctionDeclaration),
+    Struct(StructDeclaration),
+    Enum(EnumDeclaration),
 }
 
 #[derive(Debug, Clone)]
@@ -1,10 +1,18 @@
 use crate::ast::expr::{
-    AssignExpr, BinOperator, BinaryExpr, BlockExpr, BoolExpr, CallExpr, ElseBranch, Expr, ExprKind,
-    IfExpr, NumberExpr, ParenthesizedExpr, StringExpr, UnOperator, UnaryExpr, VarExpr,
+    ArrayLiteralExpr, AssignExpr, BinOperator, BinaryExpr, BlockExpr, BoolExpr, CallExpr, CastExpr,
+    ElseBranch, EnumVariantExpr, Expr, ExprKind, FieldAccessExpr, FieldInitializer, FloatExpr,
+    IfExpr, IndexExpr, MatchArm, MatchExpr, NumberExpr, ParenthesizedExpr, Pattern,
+    PlaceAssignExpr, StringExpr, StructLiteralExpr, UnOperator, UnaryExpr, VarExpr,
 };
 use crate::ast::function::{Body, FunctionParameter, FunctionType, TypeAnnotation};
 use crate::ast::item::{Item, ItemKind};
-use crate::ast::stmt::{LetStmt, ReturnStmt, Stmt, StmtKind, WhileStmt};
+use crate::ast::span::TextSpan;
+use crate::ast::stmt::{
+    BreakStmt, ContinueStmt, ForStmt, LetStmt, ReturnStmt, Stmt, StmtKind, WhileStmt,
+};
+use crate::ast::structure::{
+    EnumDeclaration, FieldDeclaration, StructDeclaration, VariantDeclaration,
+};
 use crate::lexer::token::Token;
 use crate::types::Type;
 use crate::Result;
@@ -17,6 +25,7 @@ pub mod item;
 pub mod position;
 pub mod span;
 pub mod stmt;
+pub mod structure;
 pub mod visitor;
 
 pub type ID = u32;
@@ -102,11 +111,44 @@ impl Ast {
         }))
     }
 
+    pub fn for_statement(
+        &mut self,
+        for_keyword: Token,
+        identifier: Token,
+        start: ID,
+        end: ID,
+        inclusive: bool,
+        step: Option<ID>,
+        body: Body,
+    ) -> ID {
+        let var_id = new_id(self.stmts.len() as u32);
+
+        self.new_stmt(StmtKind::For(ForStmt {
+            for_keyword,
+            identifier,
+            start,
+            end,
+            inclusive,
+            step,
+            body,
+            variable_id: var_id,
+        }))
+    }
+
+    pub fn break_statement(&mut self, break_keyword: Token) -> ID {
+        self.new_stmt(StmtKind::Break(BreakStmt { break_keyword }))
+    }
+
+    pub fn continue_statement(&mut self, continue_keyword: Token) -> ID {
+        self.new_stmt(StmtKind::Continue(ContinueStmt { continue_keyword }))
+    }
+
     pub fn new_func_item(
         &mut self,
         func_keyword: Token,
         identifier: Token,
         parameters: Vec<ID>,
+        parameter_identifiers: Vec<Token>,
         body: Body,
         return_type: Option<FunctionType>,
         function_id: ID,
@@ -115,6 +157,7 @@ impl Ast {
             func_keyword,
             identifier,
             parameters,
+            parameter_identifiers,
             body,
             return_type,
             id: function_id,
@@ -123,6 +166,36 @@ impl Ast {
         Ok(self.new_item(func_decl))
     }
 
+    pub fn new_struct_item(
+        &mut self,
+        struct_keyword: Token,
+        identifier: Token,
+        fields: Vec<FieldDeclaration>,
+        struct_id: ID,
+    ) -> &Item {
+        self.new_item(ItemKind::Struct(StructDeclaration {
+            struct_keyword,
+            identifier,
+            fields,
+            id: struct_id,
+        }))
+    }
+
+    pub fn new_enum_item(
+        &mut self,
+        enum_keyword: Token,
+        identifier: Token,
+        variants: Vec<VariantDeclaration>,
+        enum_id: ID,
+    ) -> &Item {
+        self.new_item(ItemKind::Enum(EnumDeclaration {
+            enum_keyword,
+            identifier,
+            variants,
+            id: enum_id,
+        }))
+    }
+
     pub fn expression_statement(&mut self, expr_id: ID) -> ID {
         self.new_stmt(StmtKind::Expr(expr_id))
     }
@@ -138,10 +211,35 @@ impl Ast {
         self.new_expr(ExprKind::Number(NumberExpr { number, token }))
     }
 
+    pub fn float_expression(&mut self, token: Token, number: f64) -> &Expr {
+        self.new_expr(ExprKind::Float(FloatExpr { number, token }))
+    }
+
+    pub fn cast_expression(
+        &mut self,
+        expression: ID,
+        as_keyword: Token,
+        type_name: Token,
+    ) -> &Expr {
+        self.new_expr(ExprKind::Cast(CastExpr {
+            expression,
+            as_keyword,
+            type_name,
+        }))
+    }
+
     pub fn string_expression(&mut self, token: Token, string: String) -> &Expr {
         self.new_expr(ExprKind::String(StringExpr { token, string }))
     }
 
+    /// A malformed expression the parser skipped. It is typed `Type::Error` up front, so the
+    /// type analyzer does not report it again.
+    pub fn error_expression(&mut self, span: TextSpan) -> &Expr {
+        let id = self.new_expr(ExprKind::Error(span)).id;
+        self.update_type(id, Type::Error);
+        self.query_expr(id)
+    }
+
     pub fn boolean_expression(&mut self, token: Token, value: bool) -> &Expr {
         self.new_expr(ExprKind::Boolean(BoolExpr { token, value }))
     }
@@ -242,6 +340,111 @@ impl Ast {
         }))
     }
 
+    pub fn struct_literal_expression(
+        &mut self,
+        identifier: Token,
+        left_brace: Token,
+        fields: Vec<FieldInitializer>,
+        right_brace: Token,
+    ) -> &Expr {
+        self.new_expr(ExprKind::StructLiteral(StructLiteralExpr {
+            identifier,
+            left_brace,
+            fields,
+            right_brace,
+        }))
+    }
+
+    pub fn field_access_expression(&mut self, object: ID, dot: Token, field: Token) -> &Expr {
+        self.new_expr(ExprKind::FieldAccess(FieldAccessExpr {
+            object,
+            dot,
+            field,
+        }))
+    }
+
+    pub fn place_assignment_expression(
+        &mut self,
+        target: ID,
+        equals: Token,
+        expression: ID,
+    ) -> &Expr {
+        self.new_expr(ExprKind::PlaceAssignment(PlaceAssignExpr {
+            target,
+            equals,
+            expression,
+        }))
+    }
+
+    pub fn array_literal_expression(
+        &mut self,
+        left_bracket: Token,
+        elements: Vec<ID>,
+        right_bracket: Token,
+    ) -> &Expr {
+        self.new_expr(ExprKind::ArrayLiteral(ArrayLiteralExpr {
+            left_bracket,
+            elements,
+            right_bracket,
+        }))
+    }
+
+    pub fn index_expression(
+        &mut self,
+        object: ID,
+        left_bracket: Token,
+        index: ID,
+        right_bracket: Token,
+    ) -> &Expr {
+        self.new_expr(ExprKind::Index(IndexExpr {
+            object,
+            left_bracket,
+            index,
+            right_bracket,
+        }))
+    }
+
+    pub fn enum_variant_expression(
+        &mut self,
+        enum_name: Token,
+        variant: Token,
+        arguments: Vec<ID>,
+        right_paren: Option<Token>,
+    ) -> &Expr {
+        self.new_expr(ExprKind::EnumVariant(EnumVariantExpr {
+            enum_name,
+            variant,
+            arguments,
+            right_paren,
+        }))
+    }
+
+    pub fn match_expression(
+        &mut self,
+        match_keyword: Token,
+        scrutinee: ID,
+        arms: Vec<MatchArm>,
+        right_brace: Token,
+    ) -> &Expr {
+        self.new_expr(ExprKind::Match(MatchExpr {
+            match_keyword,
+            scrutinee,
+            arms,
+            right_brace,
+        }))
+    }
+
+    pub fn set_pattern_binding(&mut self, expr_id: ID, arm: usize, binding: usize, var_id: ID) {
+        let expr = self.query_expr_mut(expr_id);
+        match &mut expr.kind {
+            ExprKind::Match(match_expr) => match &mut match_expr.arms[arm].pattern {
+                Pattern::Variant(variant) => variant.bindings[binding].variable_id = var_id,
+                _ => unreachable!("Expected variant pattern"),
+            },
+            _ => unreachable!("Expected match expression"),
+        }
+    }
+
     pub fn query_expr_mut(&mut self, id: ID) -> &mut Expr {
         self.exprs.get_mut(&id).unwrap()
     }
@@ -282,11 +485,16 @@ impl Ast {
     pub fn set_var_stmt(&mut self, stmt_id: &ID, var_id: ID) {
         let stmt = self.stmts.get_mut(stmt_id).unwrap();
 
-        if let StmtKind::Let(let_stmt) = &mut stmt.kind {
-            log::debug!("Updating let statement with variable id: {}", var_id);
-            let_stmt.variable_id = var_id;
-        } else {
-            panic!("Expected let statement");
+        match &mut stmt.kind {
+            StmtKind::Let(let_stmt) => {
+                log::debug!("Updating let statement with variable id: {}", var_id);
+                let_stmt.variable_id = var_id;
+            }
+            StmtKind::For(for_stmt) => {
+                log::debug!("Updating for statement with variable id: {}", var_id);
+                for_stmt.variable_id = var_id;
+            }
+            _ => panic!("Expected let or for statement"),
         }
     }
 
@@ -1,5 +1,6 @@
 use crate::ast::function::{Body, TypeAnnotation};
-use crate::ast::ID;
+use crate::ast::span::TextSpan;
+use crate::ast::{Ast, ID};
 use crate::lexer::token::Token;
 
 #[derive(Debug, Clone)]
@@ -16,6 +17,9 @@ pub enum StmtKind {
     Let(LetStmt),
     While(WhileStmt),
     Return(ReturnStmt),
+    For(ForStmt),
+    Break(BreakStmt),
+    Continue(ContinueStmt),
 }
 
 #[derive(Debug, Clone)]
@@ -25,6 +29,29 @@ pub struct WhileStmt {
     pub body: Body,
 }
 
+/// `for identifier in start..end step n { }`, `..=` makes the range inclusive.
+#[derive(Debug, Clone)]
+pub struct ForStmt {
+    pub for_keyword: Token,
+    pub identifier: Token,
+    pub start: ID,
+    pub end: ID,
+    pub inclusive: bool,
+    pub step: Option<ID>,
+    pub body: Body,
+    pub variable_id: ID,
+}
+
+#[derive(Debug, Clone)]
+pub struct BreakStmt {
+    pub break_keyword: Token,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContinueStmt {
+    pub continue_keyword: Token,
+}
+
 #[derive(Debug, Clone)]
 pub struct ReturnStmt {
     pub return_keyword: Token,
@@ -41,4 +68,19 @@ impl Stmt {
     pub fn new(kind: StmtKind, id: ID) -> Self {
         Stmt { kind, id }
     }
+
+    /// The expression of an expression statement, otherwise the keyword or the name of a `let`.
+    pub fn span(&self, ast: &Ast) -> TextSpan {
+        match &self.kind {
+            StmtKind::Expr(expr) => ast.query_expr(*expr).span(ast),
+            StmtKind::Let(let_statement) => let_statement.identifier.span.clone(),
+            StmtKind::While(while_statement) => while_statement.while_keyword.span.clone(),
+            StmtKind::For(for_statement) => for_statement.for_keyword.span.clone(),
+            StmtKind::Return(return_statement) => return_statement.return_keyword.span.clone(),
+            StmtKind::Break(break_statement) => break_statement.break_keyword.span.clone(),
+            StmtKind::Continue(continue_statement) => {
+                continue_statement.continue_keyword.span.clone()
+            }
+        }
+    }
 }
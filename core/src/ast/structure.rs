@@ -0,0 +1,68 @@
+use crate::ast::function::TypeAnnotation;
+use crate::ast::ID;
+use crate::lexer::token::Token;
+use crate::types::Type;
+
+#[derive(Debug, Clone)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Vec<StructField>,
+}
+
+impl Struct {
+    pub fn field(&self, name: &str) -> Option<&StructField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StructField {
+    pub name: String,
+    pub type_: Type,
+}
+
+#[derive(Debug, Clone)]
+pub struct StructDeclaration {
+    pub struct_keyword: Token,
+    pub identifier: Token,
+    pub fields: Vec<FieldDeclaration>,
+    pub id: ID,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldDeclaration {
+    pub identifier: Token,
+    pub type_annotation: TypeAnnotation,
+}
+
+#[derive(Debug, Clone)]
+pub struct Enum {
+    pub name: String,
+    pub variants: Vec<Variant>,
+}
+
+impl Enum {
+    pub fn variant(&self, name: &str) -> Option<&Variant> {
+        self.variants.iter().find(|variant| variant.name == name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub name: String,
+    pub payload: Vec<Type>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnumDeclaration {
+    pub enum_keyword: Token,
+    pub identifier: Token,
+    pub variants: Vec<VariantDeclaration>,
+    pub id: ID,
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantDeclaration {
+    pub identifier: Token,
+    pub payload: Vec<Token>,
+}
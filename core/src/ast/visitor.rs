@@ -1,11 +1,17 @@
-use crate::ast::expr::StringExpr;
+use crate::ast::expr::{
+    ArrayLiteralExpr, CastExpr, EnumVariantExpr, FieldAccessExpr, FloatExpr, IndexExpr, MatchExpr,
+    Pattern, PlaceAssignExpr, StringExpr, StructLiteralExpr,
+};
 use crate::ast::function::FunctionDeclaration;
 use crate::ast::span::TextSpan;
+use crate::ast::stmt::{BreakStmt, ContinueStmt, ForStmt};
+use crate::ast::structure::{EnumDeclaration, StructDeclaration};
 use crate::ast::{
     AssignExpr, Ast, BinaryExpr, BlockExpr, Body, BoolExpr, CallExpr, Expr, ExprKind, IfExpr,
     ItemKind, LetStmt, NumberExpr, ParenthesizedExpr, ReturnStmt, Stmt, StmtKind, UnaryExpr,
     VarExpr, WhileStmt, ID,
 };
+use crate::lexer::token::Token;
 use crate::Result;
 
 pub trait ASTWalker {
@@ -37,6 +43,12 @@ pub trait ASTWalker {
             ItemKind::Function(func_decl) => {
                 self.visit_func_decl(ast, func_decl, item.id)?;
             }
+            ItemKind::Struct(struct_decl) => {
+                self.visit_struct_decl(ast, struct_decl)?;
+            }
+            ItemKind::Enum(enum_decl) => {
+                self.visit_enum_decl(ast, enum_decl)?;
+            }
         }
 
         Ok(())
@@ -49,6 +61,18 @@ pub trait ASTWalker {
         item_id: ID,
     ) -> Result<()>;
 
+    fn visit_struct_decl(
+        &mut self,
+        _ast: &mut Ast,
+        _struct_decl: &StructDeclaration,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_enum_decl(&mut self, _ast: &mut Ast, _enum_decl: &EnumDeclaration) -> Result<()> {
+        Ok(())
+    }
+
     fn do_visit_statement(&mut self, ast: &mut Ast, statement: ID) -> Result<()> {
         let statement = ast.query_stmt(statement).clone();
         match &statement.kind {
@@ -64,6 +88,15 @@ pub trait ASTWalker {
             StmtKind::Return(stmt) => {
                 self.visit_return_statement(ast, stmt)?;
             }
+            StmtKind::For(stmt) => {
+                self.visit_for_statement(ast, stmt, &statement)?;
+            }
+            StmtKind::Break(stmt) => {
+                self.visit_break_statement(ast, stmt)?;
+            }
+            StmtKind::Continue(stmt) => {
+                self.visit_continue_statement(ast, stmt)?;
+            }
         }
 
         Ok(())
@@ -87,6 +120,38 @@ pub trait ASTWalker {
         Ok(())
     }
 
+    fn visit_for_statement(
+        &mut self,
+        ast: &mut Ast,
+        for_statement: &ForStmt,
+        _stmt: &Stmt,
+    ) -> Result<()> {
+        self.visit_expression(ast, for_statement.start)?;
+        self.visit_expression(ast, for_statement.end)?;
+        if let Some(step) = for_statement.step {
+            self.visit_expression(ast, step)?;
+        }
+        self.visit_body(ast, &for_statement.body)?;
+
+        Ok(())
+    }
+
+    fn visit_break_statement(
+        &mut self,
+        _ast: &mut Ast,
+        _break_statement: &BreakStmt,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        _ast: &mut Ast,
+        _continue_statement: &ContinueStmt,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_block_expr(
         &mut self,
         ast: &mut Ast,
@@ -133,6 +198,9 @@ pub trait ASTWalker {
             ExprKind::Number(number) => {
                 self.visit_number_expression(ast, number, &expression)?;
             }
+            ExprKind::Float(number) => {
+                self.visit_float_expression(ast, number, &expression)?;
+            }
             ExprKind::Binary(expr) => {
                 self.visit_binary_expression(ast, expr, &expression)?;
             }
@@ -167,7 +235,31 @@ pub trait ASTWalker {
                 self.visit_string_expression(ast, string_expr, &expression)?;
             }
             ExprKind::ScopedIdentifier { path } => {
-                //     TODO:
+                self.visit_scoped_identifier_expression(ast, path, &expression)?;
+            }
+            ExprKind::StructLiteral(literal) => {
+                self.visit_struct_literal_expression(ast, literal, &expression)?;
+            }
+            ExprKind::FieldAccess(access) => {
+                self.visit_field_access_expression(ast, access, &expression)?;
+            }
+            ExprKind::PlaceAssignment(assign) => {
+                self.visit_place_assignment_expression(ast, assign, &expression)?;
+            }
+            ExprKind::ArrayLiteral(literal) => {
+                self.visit_array_literal_expression(ast, literal, &expression)?;
+            }
+            ExprKind::Index(index) => {
+                self.visit_index_expression(ast, index, &expression)?;
+            }
+            ExprKind::Cast(cast) => {
+                self.visit_cast_expression(ast, cast, &expression)?;
+            }
+            ExprKind::EnumVariant(variant) => {
+                self.visit_enum_variant_expression(ast, variant, &expression)?;
+            }
+            ExprKind::Match(match_expr) => {
+                self.visit_match_expression(ast, match_expr, &expression)?;
             }
         }
 
@@ -203,6 +295,115 @@ pub trait ASTWalker {
         Ok(())
     }
 
+    fn visit_struct_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        struct_literal: &StructLiteralExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        for field in &struct_literal.fields {
+            self.visit_expression(ast, field.value)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_field_access_expression(
+        &mut self,
+        ast: &mut Ast,
+        field_access: &FieldAccessExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, field_access.object)?;
+
+        Ok(())
+    }
+
+    fn visit_place_assignment_expression(
+        &mut self,
+        ast: &mut Ast,
+        place_assignment: &PlaceAssignExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, place_assignment.target)?;
+        self.visit_expression(ast, place_assignment.expression)?;
+
+        Ok(())
+    }
+
+    fn visit_array_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        array_literal: &ArrayLiteralExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        for element in &array_literal.elements {
+            self.visit_expression(ast, *element)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_index_expression(
+        &mut self,
+        ast: &mut Ast,
+        index_expression: &IndexExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, index_expression.object)?;
+        self.visit_expression(ast, index_expression.index)?;
+
+        Ok(())
+    }
+
+    fn visit_cast_expression(
+        &mut self,
+        ast: &mut Ast,
+        cast: &CastExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, cast.expression)
+    }
+
+    fn visit_enum_variant_expression(
+        &mut self,
+        ast: &mut Ast,
+        enum_variant: &EnumVariantExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        for argument in &enum_variant.arguments {
+            self.visit_expression(ast, *argument)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_scoped_identifier_expression(
+        &mut self,
+        _ast: &mut Ast,
+        _path: &[Token],
+        _expr: &Expr,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_match_expression(
+        &mut self,
+        ast: &mut Ast,
+        match_expr: &MatchExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, match_expr.scrutinee)?;
+        for arm in &match_expr.arms {
+            if let Pattern::Literal(literal) = arm.pattern {
+                self.visit_expression(ast, literal)?;
+            }
+            self.visit_expression(ast, arm.body)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_variable_expression(
         &mut self,
         ast: &mut Ast,
@@ -217,6 +418,13 @@ pub trait ASTWalker {
         expr: &Expr,
     ) -> Result<()>;
 
+    fn visit_float_expression(
+        &mut self,
+        ast: &mut Ast,
+        number: &FloatExpr,
+        expr: &Expr,
+    ) -> Result<()>;
+
     fn visit_string_expression(
         &mut self,
         ast: &mut Ast,
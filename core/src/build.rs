@@ -1,12 +1,13 @@
 use crate::ast::visitor::ASTWalker;
 use crate::ast::Ast;
 use crate::codegen::CppCodegen;
-use crate::error::error::Error::{MainFunctionParameters, ParseError};
+use crate::error::diagnostics::Diagnostics;
 use crate::global_context::GlobalContext;
-use crate::lexer::token::{Token, TokenKind};
+use crate::interpreter::{Environment, Interpreter};
 use crate::lexer::Lexer;
 use crate::parser::Parser;
 use crate::scopes::Scopes;
+use crate::semantic::lint::Linter;
 use crate::semantic::types::TypeAnalyzer;
 use crate::Result;
 use log::debug;
@@ -17,6 +18,8 @@ pub struct BuildProcess {
     pub ast: &'static mut Ast,
     pub ctx: &'static mut GlobalContext,
     pub file: PathBuf,
+    /// Fail on warnings, reporting them as errors.
+    pub deny_warnings: bool,
 }
 
 impl BuildProcess {
@@ -26,51 +29,65 @@ impl BuildProcess {
             ast: Box::leak(Box::new(Ast::new())),
             ctx: Box::leak(Box::new(GlobalContext::new())),
             file,
+            deny_warnings: false,
         }
     }
 
-    pub fn compile(&mut self) -> Result<String> {
+    /// Lexes, parses and type checks the input, filling `ast` and `ctx`. Returns the warnings,
+    /// which do not stop the build unless `deny_warnings` is set.
+    pub fn analyze(&mut self) -> Result<Diagnostics> {
         log::debug!("Starting compilation process");
-        let mut tokens: Vec<Token> = vec![];
-        let mut lexer = Lexer::new(&self.input);
-
-        while let Some(token) = lexer.next_token() {
-            if token.kind == TokenKind::Whitespace {
-                continue;
-            }
-            if token.kind == TokenKind::Eof {
-                tokens.push(token);
-                break;
-            }
-            tokens.push(token);
-        }
+        let tokens = Lexer::new(&self.input).tokenize()?;
         log::debug!("Finished lexical analysis with {} tokens", tokens.len());
 
         let mut parser = Parser::new(tokens, self.input.clone(), self.ast, self.ctx);
+        let parsed = parser.parse();
+        let mut diagnostics = std::mem::take(&mut parser.diagnostics);
 
-        match parser.parse() {
+        match parsed {
             Ok(_) => {
                 log::debug!("Finished parsing");
                 let scopes = Scopes::new(self.ctx);
-                let mut type_analyzer = TypeAnalyzer {
-                    content: self.input.clone(),
-                    scopes,
-                };
+                let mut type_analyzer = TypeAnalyzer::new(self.input.clone(), scopes);
 
-                for (id, _) in self.ast.items.clone().iter() {
-                    type_analyzer.visit_item(self.ast, *id)?;
-                }
-                let mut codegen = CppCodegen::new(self.ast, self.file.clone(), self.ctx);
-
-                let code = codegen.generate_code()?;
-
-                Ok(code)
+                type_analyzer.analyze(self.ast);
+                diagnostics.extend(type_analyzer.diagnostics);
             }
             Err(e) => {
                 log::debug!("Error parsing: {}", e);
 
-                Err(e)
+                diagnostics.report(e);
             }
         }
+
+        // Everything found is reported at once, in the order it appears in the file.
+        diagnostics.into_result()?;
+
+        let mut linter = Linter::new(self.input.clone(), self.ctx);
+        linter.lint(self.ast);
+        let mut warnings = linter.diagnostics;
+        if self.deny_warnings {
+            warnings.deny_warnings();
+            warnings.into_result()?;
+            return Ok(Diagnostics::new());
+        }
+
+        Ok(warnings)
+    }
+
+    /// Generates C++ for the program checked by `analyze`.
+    pub fn compile(&mut self) -> Result<String> {
+        let mut codegen = CppCodegen::new(self.ast, self.file.clone(), self.ctx);
+        let code = codegen.generate_code()?;
+
+        Ok(code)
+    }
+
+    /// Runs the program checked by `analyze` with the tree-walking interpreter instead of
+    /// generating C++, passing `args` to `main`. Returns the exit status of the program.
+    pub fn interpret(&mut self, args: Vec<String>) -> Result<i32> {
+        log::debug!("Starting interpreter");
+        let mut interpreter = Interpreter::new(self.ctx, self.input.clone(), Environment::new());
+        interpreter.run(self.ast, args)
     }
 }
@@ -0,0 +1,17 @@
+use crate::ast::ID;
+
+/// Prefix of every user identifier in the generated C++. No C++ keyword, no name of the
+/// runtime in `cli/lib` and no name or macro of the headers it includes starts with it, so a
+/// Pulse name can never collide with one of them.
+const PREFIX: &str = "pulse";
+
+/// Name of a user identifier in the generated C++, `count` becomes `pulse_count`.
+pub(crate) fn mangle(name: &str) -> String {
+    format!("{}_{}", PREFIX, name)
+}
+
+/// Name of a variable that needs its ID to be unique, `pulse12_x`. A name from `mangle` never
+/// has a digit after the prefix, so the two cannot meet.
+pub(crate) fn mangle_with_id(name: &str, id: ID) -> String {
+    format!("{}{}_{}", PREFIX, id, name)
+}
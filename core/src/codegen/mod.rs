@@ -1,12 +1,18 @@
+use crate::ast::item::ItemKind;
+use crate::ast::structure::{Enum, Struct};
 use crate::ast::visitor::ASTWalker;
-use crate::ast::Ast;
+use crate::ast::{Ast, ID};
 use crate::Result;
+use std::collections::HashMap;
 use std::fmt::Write;
 use std::path::PathBuf;
 
+mod mangle;
 pub mod walker;
 
 use crate::global_context::GlobalContext;
+use crate::types::Type;
+use mangle::mangle;
 use walker::*;
 
 pub struct CppCodegen<'a> {
@@ -14,6 +20,9 @@ pub struct CppCodegen<'a> {
     pub file: PathBuf,
     pub output: String,
     pub ctx: &'a mut GlobalContext,
+    /// Expressions already evaluated ahead of their statement, with the temporary holding
+    /// each value. See `hoist`.
+    hoisted: HashMap<ID, String>,
 }
 
 impl<'a> CppCodegen<'a> {
@@ -23,27 +32,251 @@ impl<'a> CppCodegen<'a> {
             file,
             output: String::new(),
             ctx,
+            hoisted: HashMap::new(),
         }
     }
 
     pub fn write_prelude(&mut self) -> Result<()> {
         writeln!(self.output, "#include <iostream>")?;
         writeln!(self.output, "#include <string>")?;
+        writeln!(self.output, "#include <variant>")?;
+        writeln!(self.output, "#include <vector>")?;
+        writeln!(self.output, "#include <cstdlib>")?;
         writeln!(self.output, "#include \"../std/lib.cpp\"")?;
 
-        writeln!(self.output, "using namespace std;")?;
+        Ok(())
+    }
+
+    /// Writes every struct and enum before the functions that use it. A type is emitted after
+    /// the types it contains, since C++ needs complete types for members.
+    pub fn write_types(&mut self) -> Result<()> {
+        let mut emitted: Vec<String> = vec![];
+        let names: Vec<String> = self
+            .ctx
+            .structs
+            .values()
+            .map(|structure| structure.name.clone())
+            .chain(
+                self.ctx
+                    .enums
+                    .values()
+                    .map(|enumeration| enumeration.name.clone()),
+            )
+            .collect();
+        for name in names {
+            self.write_type(&name, &mut emitted)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_type(&mut self, name: &str, emitted: &mut Vec<String>) -> Result<()> {
+        if emitted.iter().any(|emitted| emitted == name) {
+            return Ok(());
+        }
+        emitted.push(name.to_string());
+
+        if let Some(structure) = self.ctx.get_struct(name).cloned() {
+            for field in &structure.fields {
+                self.write_dependency(&field.type_, emitted)?;
+            }
+            self.write_struct(&structure)
+        } else if let Some(enumeration) = self.ctx.get_enum(name).cloned() {
+            for variant in &enumeration.variants {
+                for type_ in &variant.payload {
+                    self.write_dependency(type_, emitted)?;
+                }
+            }
+            self.write_enum(&enumeration)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn write_dependency(&mut self, type_: &Type, emitted: &mut Vec<String>) -> Result<()> {
+        match type_ {
+            Type::Struct(name) | Type::Enum(name) => self.write_type(name, emitted),
+            Type::Array(element) => self.write_dependency(element, emitted),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_struct(&mut self, structure: &Struct) -> Result<()> {
+        let name = mangle(&structure.name);
+        writeln!(self.output, "struct {} {{", name)?;
+        for field in &structure.fields {
+            writeln!(
+                self.output,
+                "    {} {};",
+                type_to_str(field.type_.clone()),
+                mangle(&field.name)
+            )?;
+        }
+        writeln!(self.output, "}};")?;
+
+        // Printing a struct matches the interpreter, `Point { x: 1, y: 2 }`.
+        writeln!(
+            self.output,
+            "std::ostream& operator<<(std::ostream& out, const {}& value) {{",
+            name
+        )?;
+        write!(
+            self.output,
+            "    out << std::boolalpha << \"{} {{ \"",
+            structure.name
+        )?;
+        for (i, field) in structure.fields.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            write!(
+                self.output,
+                " << \"{}{}: \" << value.{}",
+                separator,
+                field.name,
+                mangle(&field.name)
+            )?;
+        }
+        writeln!(self.output, " << \" }}\";")?;
+        writeln!(self.output, "    return out;\n}}")?;
+
+        Ok(())
+    }
+
+    /// An enum becomes a struct holding a `std::variant` of one nested struct per variant,
+    /// payload values are stored in the fields `_0`, `_1`, ...
+    fn write_enum(&mut self, enumeration: &Enum) -> Result<()> {
+        let name = mangle(&enumeration.name);
+        writeln!(self.output, "struct {} {{", name)?;
+        for variant in &enumeration.variants {
+            write!(self.output, "    struct {} {{", mangle(&variant.name))?;
+            for (i, type_) in variant.payload.iter().enumerate() {
+                // Qualified, so a payload type named like the variant is not the variant itself.
+                let type_name = match type_ {
+                    Type::Struct(name) | Type::Enum(name) => format!("::{}", mangle(name)),
+                    _ => type_to_str(type_.clone()),
+                };
+                write!(self.output, " {} _{};", type_name, i)?;
+            }
+            writeln!(self.output, " }};")?;
+        }
+        let variants: Vec<String> = enumeration
+            .variants
+            .iter()
+            .map(|v| mangle(&v.name))
+            .collect();
+        writeln!(
+            self.output,
+            "    std::variant<{}> value;",
+            variants.join(", ")
+        )?;
+        writeln!(self.output, "}};")?;
+
+        // Printing an enum matches the interpreter, `Circle(2)` or `Empty`.
+        writeln!(
+            self.output,
+            "std::ostream& operator<<(std::ostream& out, const {}& value) {{",
+            name
+        )?;
+        writeln!(self.output, "    out << std::boolalpha;")?;
+        for variant in &enumeration.variants {
+            write!(
+                self.output,
+                "    if (auto* v = std::get_if<{}::{}>(&value.value)) out << \"{}",
+                name,
+                mangle(&variant.name),
+                variant.name
+            )?;
+            if variant.payload.is_empty() {
+                writeln!(self.output, "\";")?;
+                continue;
+            }
+            write!(self.output, "(\"")?;
+            for i in 0..variant.payload.len() {
+                let separator = if i == 0 { "" } else { " << \", \"" };
+                write!(self.output, "{} << v->_{}", separator, i)?;
+            }
+            writeln!(self.output, " << \")\";")?;
+        }
+        writeln!(self.output, "    return out;\n}}")?;
+
+        Ok(())
+    }
+
+    /// Declares every function before any body, so functions can call each other regardless of
+    /// the order they are defined in.
+    pub fn write_prototypes(&mut self) -> Result<()> {
+        let functions: Vec<ID> = self.ctx.functions.keys().copied().collect();
+        for function in functions {
+            self.write_signature(function)?;
+            writeln!(self.output, ";")?;
+        }
+
+        Ok(())
+    }
+
+    /// Declares the variables of top-level `let`s at file scope, so every function can use
+    /// them. They are assigned by the top-level statements at the start of `main`.
+    pub fn write_globals(&mut self) -> Result<()> {
+        for id in self.ctx.global_variables.clone() {
+            let var = self.ctx.variables.get(&id).unwrap();
+            writeln!(
+                self.output,
+                "{} {};",
+                type_to_str(var.type_.clone()),
+                variable_name(id, var)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the C++ `main`. It runs the top-level statements and then the Pulse `main`,
+    /// emitted as `pulse_main`, passing it the arguments after the program name and returning its
+    /// status. Without `fn main` the file is a script and only the statements run.
+    fn write_main(&mut self, ast: &mut Ast) -> Result<()> {
+        writeln!(self.output, "int main(int argc, char** argv) {{")?;
+        self.write_top_level_statements(ast)?;
+
+        let main = self
+            .ctx
+            .lookup_function("main")
+            .map(|main| self.ctx.functions[&main].clone());
+        match main {
+            None => writeln!(self.output, "return 0;")?,
+            Some(main) if matches!(main.return_type, Type::Void) => {
+                writeln!(self.output, "{}();\nreturn 0;", mangle("main"))?
+            }
+            Some(main) if main.parameters.is_empty() => {
+                writeln!(self.output, "return {}();", mangle("main"))?
+            }
+            Some(_) => writeln!(
+                self.output,
+                "return {}(std::vector<std::string>(argv + 1, argv + argc));",
+                mangle("main")
+            )?,
+        }
+        writeln!(self.output, "}}")?;
 
         Ok(())
     }
 
     pub fn generate_code(&mut self) -> Result<String> {
         self.write_prelude()?;
+        self.write_types()?;
+        self.write_globals()?;
+        self.write_prototypes()?;
         let ast_ptr: *mut Ast = self.ast as *mut Ast;
-        for (id, _) in self.ast.items.clone().iter() {
+        for (id, item) in self.ast.items.clone().iter() {
+            // Top-level statements are written at the start of `main`.
+            if let ItemKind::Stmt(_) = item.kind {
+                continue;
+            }
             unsafe {
                 self.visit_item(&mut *ast_ptr, *id)?;
             }
         }
+        unsafe {
+            self.write_main(&mut *ast_ptr)?;
+        }
 
         Ok(self.output.clone())
     }
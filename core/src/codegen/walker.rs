@@ -1,28 +1,134 @@
 use crate::ast::expr::{
-    BinOpKind, BinOperator, BinaryExpr, BoolExpr, CallExpr, Expr, ExprKind, IfExpr, NumberExpr,
-    StringExpr, UnaryExpr, VarExpr,
+    ArrayLiteralExpr, AssignExpr, BinOpAssociativity, BinOpKind, BinOperator, BinaryExpr,
+    BlockExpr, BoolExpr, CallExpr, CastExpr, EnumVariantExpr, Expr, ExprKind, FieldAccessExpr,
+    FloatExpr, IfExpr, IndexExpr, MatchExpr, NumberExpr, ParenthesizedExpr, Pattern,
+    PlaceAssignExpr, StringExpr, StructLiteralExpr, UnOpKind, UnaryExpr, VarExpr,
 };
 use crate::ast::function::FunctionDeclaration;
+use crate::ast::item::ItemKind;
 use crate::ast::span::TextSpan;
-use crate::ast::stmt::{LetStmt, ReturnStmt, Stmt};
+use crate::ast::stmt::{
+    BreakStmt, ContinueStmt, ForStmt, LetStmt, ReturnStmt, Stmt, StmtKind, WhileStmt,
+};
 use crate::ast::visitor::ASTWalker;
 use crate::ast::{Ast, ID};
+use crate::codegen::mangle::{mangle, mangle_with_id};
 use crate::codegen::CppCodegen;
+use crate::error::error::Error::Internal;
+use crate::global_context::Variable;
+use crate::interpreter::stdlib;
 use crate::semantic::types::STD_RESERVED_WORDS;
 use crate::types::Type;
 use crate::Result;
 use std::fmt::Write;
 
-fn type_to_str(type_: Type) -> String {
+pub(crate) fn type_to_str(type_: Type) -> String {
     match type_ {
         Type::Int => "int".to_string(),
+        Type::Float => "double".to_string(),
         Type::String => "std::string".to_string(),
         Type::Bool => "bool".to_string(),
         Type::Void => "void".to_string(),
+        Type::Struct(name) | Type::Enum(name) => mangle(&name),
+        Type::Array(element) => format!("std::vector<{}>", type_to_str(*element)),
         _ => panic!("Invalid type"),
     }
 }
 
+/// Name of a variable in the generated C++. C++ cannot redeclare a name in the same block and
+/// a nested declaration hides the outer one from its own initializer, so a variable that
+/// shadows another gets its ID in its name, `pulse12_x`.
+pub(crate) fn variable_name(id: ID, var: &Variable) -> String {
+    if var.shadowing {
+        mangle_with_id(&var.name, id)
+    } else {
+        mangle(&var.name)
+    }
+}
+
+/// Writes a decoded Pulse string as a C++ literal. Anything outside printable ASCII becomes
+/// an octal escape of its UTF-8 bytes, octal escapes end after three digits so the next
+/// character can never extend them.
+fn escape_string(string: &str) -> String {
+    let mut escaped = String::new();
+    for byte in string.bytes() {
+        match byte {
+            b'\\' => escaped.push_str("\\\\"),
+            b'"' => escaped.push_str("\\\""),
+            b'\n' => escaped.push_str("\\n"),
+            b'\t' => escaped.push_str("\\t"),
+            b' '..=b'~' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    escaped
+}
+
+/// Precedence of a binary operator in C++, higher binds tighter. Bitwise operators bind
+/// looser than comparisons in C++, unlike in Pulse.
+fn cpp_binary_precedence(kind: &BinOpKind) -> u8 {
+    match kind {
+        // Emitted as a call to `math::ipow`.
+        BinOpKind::Power => u8::MAX,
+        BinOpKind::Multiply | BinOpKind::Divide | BinOpKind::Modulo => 13,
+        BinOpKind::Plus | BinOpKind::Minus => 12,
+        BinOpKind::LessThan
+        | BinOpKind::LessThanOrEqual
+        | BinOpKind::GreaterThan
+        | BinOpKind::GreaterThanOrEqual => 10,
+        BinOpKind::Equals | BinOpKind::NotEquals => 9,
+        BinOpKind::BitwiseAnd => 8,
+        BinOpKind::BitwiseXor => 7,
+        BinOpKind::BitwiseOr => 6,
+        BinOpKind::LogicalAnd => 5,
+        BinOpKind::LogicalOr => 4,
+    }
+}
+
+/// Precedence of the C++ emitted for `expr`. Unary operands, calls and literals are never
+/// split by a surrounding operator.
+fn cpp_precedence(ast: &Ast, expr: ID) -> u8 {
+    match &ast.query_expr(expr).kind {
+        ExprKind::Binary(binary) if is_runtime_call(ast, binary) => u8::MAX,
+        ExprKind::Binary(binary) => cpp_binary_precedence(&binary.operator.kind),
+        ExprKind::Assignment(_) | ExprKind::PlaceAssignment(_) => 2,
+        _ => u8::MAX,
+    }
+}
+
+/// Value of an integer expression built from literals, `-` and `**` only. Folded with the
+/// interpreter's `power`, which wraps around at 32 bits like `math::ipow` in
+/// `cli/lib/math.cpp`, so both backends agree on it.
+fn constant_int(ast: &Ast, expr: ID) -> Option<i32> {
+    match &ast.query_expr(expr).kind {
+        ExprKind::Number(number) => i32::try_from(number.number).ok(),
+        ExprKind::Parenthesized(parenthesized) => constant_int(ast, parenthesized.inner),
+        ExprKind::Unary(unary) if matches!(unary.operator.kind, UnOpKind::Minus) => {
+            constant_int(ast, unary.operand).map(i32::wrapping_neg)
+        }
+        ExprKind::Binary(binary) if matches!(binary.operator.kind, BinOpKind::Power) => {
+            Some(stdlib::power(
+                constant_int(ast, binary.left)?,
+                constant_int(ast, binary.right)?,
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn strip_parentheses(ast: &Ast, mut expr: ID) -> ID {
+    while let ExprKind::Parenthesized(parenthesized) = &ast.query_expr(expr).kind {
+        expr = parenthesized.inner;
+    }
+    expr
+}
+
+/// Where the value of an expression in tail position goes.
+enum Tail {
+    Return,
+    Assign(String),
+}
+
 fn to_operator(op: BinOperator) -> &'static str {
     match op.kind {
         BinOpKind::Plus => "+",
@@ -39,54 +145,590 @@ fn to_operator(op: BinOperator) -> &'static str {
         BinOpKind::LessThanOrEqual => "<=",
         BinOpKind::GreaterThanOrEqual => ">=",
         BinOpKind::Modulo => "%",
-        BinOpKind::Power => "**",
+        BinOpKind::Power => unreachable!("`**` is emitted as a call to math::ipow"),
+        BinOpKind::LogicalAnd => "&&",
+        BinOpKind::LogicalOr => "||",
     }
 }
 
-impl ASTWalker for CppCodegen<'_> {
-    fn visit_func_decl(
+/// Whether a binary operator is emitted as a call to the runtime in `cli/lib/math.cpp`, like
+/// `**` and integer `/` and `%`.
+fn is_runtime_call(ast: &Ast, binary: &BinaryExpr) -> bool {
+    match binary.operator.kind {
+        BinOpKind::Power => true,
+        BinOpKind::Divide | BinOpKind::Modulo => {
+            matches!(ast.query_expr(binary.left).ty, Type::Int)
+        }
+        _ => false,
+    }
+}
+
+impl CppCodegen<'_> {
+    /// Writes an operand of a binary operator with `precedence`, parenthesized only when C++
+    /// would otherwise group it differently. Parentheses from the source are dropped here and
+    /// inserted again where needed.
+    fn write_operand(
         &mut self,
         ast: &mut Ast,
-        func_decl: &FunctionDeclaration,
-        item_id: ID,
+        operand: ID,
+        precedence: u8,
+        parenthesize_equal: bool,
     ) -> Result<()> {
-        let func = self.ctx.functions.get(&item_id).unwrap();
+        let operand = strip_parentheses(ast, operand);
+
+        let operand_precedence = cpp_precedence(ast, operand);
+        if operand_precedence < precedence || operand_precedence == precedence && parenthesize_equal
+        {
+            write!(self.output, "(")?;
+            self.visit_expression(ast, operand)?;
+            write!(self.output, ")")?;
+        } else {
+            self.visit_expression(ast, operand)?;
+        }
+
+        Ok(())
+    }
 
-        let type_name = func.return_type.to_string();
-        let name = func.name.to_string();
+    /// Writes `expr` in tail position, handing its value to `tail`. Blocks, `if`/`else` and
+    /// `match` do so from each branch instead, since they are statements in C++.
+    fn write_tail(&mut self, ast: &mut Ast, expr: ID, tail: &Tail) -> Result<()> {
+        let expr = strip_parentheses(ast, expr);
+        match ast.query_expr(expr).kind.clone() {
+            ExprKind::Block(block) => {
+                write!(self.output, "{{\n")?;
+                self.write_tail_body(ast, &block.stmts, tail)?;
+                write!(self.output, "}}\n")?;
+            }
+            ExprKind::Match(match_expr) => self.write_match(ast, &match_expr, expr, Some(tail))?,
+            ExprKind::If(IfExpr {
+                condition,
+                then_branch,
+                else_branch: Some(else_branch),
+                ..
+            }) => {
+                self.hoist(ast, condition)?;
+                write!(self.output, "if (")?;
+                self.visit_expression(ast, condition)?;
+                write!(self.output, ") {{\n")?;
+                self.write_tail_body(ast, &then_branch.stmts, tail)?;
+                write!(self.output, "}} else {{\n")?;
+                self.write_tail_body(ast, &else_branch.body.stmts, tail)?;
+                write!(self.output, "}}\n")?;
+            }
+            _ => {
+                self.hoist(ast, expr)?;
+                match tail {
+                    Tail::Return => write!(self.output, "return ")?,
+                    Tail::Assign(name) => write!(self.output, "{} = ", name)?,
+                }
+                self.visit_expression(ast, expr)?;
+                write!(self.output, ";\n")?;
+            }
+        }
 
-        write!(self.output, "{} {}(", type_name, name)?;
+        Ok(())
+    }
+
+    /// Writes statements whose trailing expression, if it has a value, goes to `tail`.
+    fn write_tail_body(&mut self, ast: &mut Ast, stmts: &[ID], tail: &Tail) -> Result<()> {
+        for (i, stmt) in stmts.iter().enumerate() {
+            match ast.query_stmt(*stmt).kind {
+                StmtKind::Expr(expr)
+                    if i + 1 == stmts.len() && !matches!(ast.query_expr(expr).ty, Type::Void) =>
+                {
+                    self.write_tail(ast, expr, tail)?
+                }
+                _ => self.visit_statement(ast, *stmt)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_arm_body(&mut self, ast: &mut Ast, body: ID, tail: Option<&Tail>) -> Result<()> {
+        if let Some(tail) = tail {
+            return self.write_tail(ast, body, tail);
+        }
+
+        match &ast.query_expr(body).kind {
+            ExprKind::Block(block) => {
+                for stmt in block.stmts.clone() {
+                    self.visit_statement(ast, stmt)?;
+                }
+            }
+            _ => {
+                self.write_expression_statement(ast, body)?;
+                write!(self.output, ";\n")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the return type, name and parameters of a function, shared by its prototype and
+    /// its definition.
+    pub(crate) fn write_signature(&mut self, function: ID) -> Result<()> {
+        let func = self.ctx.functions.get(&function).unwrap();
+
+        let type_name = type_to_str(func.return_type.clone());
+        write!(self.output, "{} {}(", type_name, mangle(&func.name))?;
 
         for (i, param) in func.parameters.iter().enumerate() {
             if i != 0 {
                 write!(self.output, ", ")?;
             }
 
-            let param = self.ctx.variables.get(param).unwrap();
-            let param_type = type_to_str(param.type_.clone());
+            let var = self.ctx.variables.get(param).unwrap();
+            let param_type = type_to_str(var.type_.clone());
+
+            write!(self.output, "{} {}", param_type, variable_name(*param, var))?;
+        }
+
+        write!(self.output, ")")?;
+
+        Ok(())
+    }
 
-            write!(self.output, "{} {}", param_type, param.name)?;
+    /// Writes the top-level statements in order, they run before anything else in `main`.
+    pub(crate) fn write_top_level_statements(&mut self, ast: &mut Ast) -> Result<()> {
+        for item in ast.items.values().cloned().collect::<Vec<_>>() {
+            if let ItemKind::Stmt(stmt) = item.kind {
+                self.visit_statement(ast, stmt)?;
+            }
         }
 
+        Ok(())
+    }
+
+    fn write_if_statement(&mut self, ast: &mut Ast, if_expr: &IfExpr) -> Result<()> {
+        self.hoist(ast, if_expr.condition)?;
+        write!(self.output, "if (")?;
+        self.visit_expression(ast, if_expr.condition)?;
         write!(self.output, ") {{\n")?;
-        let body = func.body.stmts.clone();
 
-        for stmt in body {
+        for stmt in if_expr.then_branch.stmts.clone() {
+            self.visit_statement(ast, stmt)?;
+        }
+
+        if let Some(else_branch) = &if_expr.else_branch {
+            write!(self.output, "}} else {{\n")?;
+
+            for stmt in else_branch.body.stmts.clone() {
+                self.visit_statement(ast, stmt)?;
+            }
+        }
+
+        write!(self.output, "}}\n")?;
+
+        Ok(())
+    }
+
+    fn write_block_statement(&mut self, ast: &mut Ast, block: &BlockExpr) -> Result<()> {
+        write!(self.output, "{{\n")?;
+
+        for stmt in block.stmts.clone() {
             self.visit_statement(ast, stmt)?;
         }
 
+        write!(self.output, "}}\n")?;
+
+        Ok(())
+    }
+
+    /// Operands of `expr` in the order they are evaluated. The place an assignment or an
+    /// `std::array` function writes to is not an operand, only the indices inside it are.
+    fn operands(&self, ast: &Ast, expr: ID) -> Vec<ID> {
+        match &ast.query_expr(expr).kind {
+            ExprKind::Binary(binary) => vec![binary.left, binary.right],
+            ExprKind::Unary(unary) => vec![unary.operand],
+            ExprKind::Parenthesized(parenthesized) => vec![parenthesized.inner],
+            ExprKind::Cast(cast) => vec![cast.expression],
+            ExprKind::FieldAccess(access) => vec![access.object],
+            ExprKind::Index(index) => vec![index.object, index.index],
+            ExprKind::Assignment(assignment) => vec![assignment.expression],
+            ExprKind::PlaceAssignment(assignment) => {
+                let mut operands = vec![assignment.expression];
+                operands.extend(place_operands(ast, assignment.target));
+                operands
+            }
+            ExprKind::Call(call) if mutates_first_argument(ast, call) => {
+                let mut operands = place_operands(ast, call.arguments[0]);
+                operands.extend(&call.arguments[1..]);
+                operands
+            }
+            ExprKind::Call(call) => call.arguments.clone(),
+            ExprKind::ArrayLiteral(array) => array.elements.clone(),
+            ExprKind::EnumVariant(variant) => variant.arguments.clone(),
+            // In declaration order, like the aggregate initializer.
+            ExprKind::StructLiteral(literal) => self
+                .ctx
+                .get_struct(literal.struct_name())
+                .map(|structure| {
+                    structure
+                        .fields
+                        .iter()
+                        .filter_map(|field| {
+                            literal
+                                .fields
+                                .iter()
+                                .find(|initializer| {
+                                    initializer.identifier.span.literal == field.name
+                                })
+                                .map(|initializer| initializer.value)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+
+    /// Whether `expr` holds an `if`, block or `match` that produces a value, or operands C++
+    /// could evaluate out of order. C++ has no such expressions and no fixed order for them,
+    /// `hoist` evaluates them ahead of the statement.
+    fn needs_hoisting(&self, ast: &Ast, expr: ID) -> bool {
+        if self.hoisted.contains_key(&expr) {
+            return false;
+        }
+
+        let expression = ast.query_expr(expr);
+        match expression.kind {
+            ExprKind::If(_) | ExprKind::Block(_) | ExprKind::Match(_) => {
+                !matches!(expression.ty, Type::Void)
+            }
+            _ => {
+                self.needs_ordering(ast, expr)
+                    || self
+                        .operands(ast, expr)
+                        .into_iter()
+                        .any(|operand| self.needs_hoisting(ast, operand))
+            }
+        }
+    }
+
+    /// Whether the operands of `expr` have to be evaluated one by one to run left to right,
+    /// like in the interpreter. C++ leaves the order of call arguments and of the operands of
+    /// arithmetic and comparisons unspecified, which only shows when one has a side effect.
+    fn needs_ordering(&self, ast: &Ast, expr: ID) -> bool {
+        let unordered = match &ast.query_expr(expr).kind {
+            ExprKind::Binary(binary) => !matches!(
+                binary.operator.kind,
+                BinOpKind::LogicalAnd | BinOpKind::LogicalOr
+            ),
+            ExprKind::Call(_) => true,
+            _ => false,
+        };
+        if !unordered {
+            return false;
+        }
+
+        let operands = self.operands(ast, expr);
+        let varying = operands
+            .iter()
+            .filter(|operand| !self.is_constant(ast, **operand))
+            .count();
+        varying > 1
+            && operands
+                .iter()
+                .any(|operand| self.has_side_effects(ast, *operand))
+    }
+
+    /// Whether evaluating `expr` can change state or print. Calls count even when the function
+    /// turns out to be pure.
+    fn has_side_effects(&self, ast: &Ast, expr: ID) -> bool {
+        if self.hoisted.contains_key(&expr) {
+            return false;
+        }
+
+        match &ast.query_expr(expr).kind {
+            ExprKind::Call(_)
+            | ExprKind::Assignment(_)
+            | ExprKind::PlaceAssignment(_)
+            | ExprKind::If(_)
+            | ExprKind::Block(_)
+            | ExprKind::Match(_) => true,
+            _ => self
+                .operands(ast, expr)
+                .into_iter()
+                .any(|operand| self.has_side_effects(ast, operand)),
+        }
+    }
+
+    /// Whether `expr` has the same value wherever it is evaluated, a literal or a temporary.
+    fn is_constant(&self, ast: &Ast, expr: ID) -> bool {
+        let expr = strip_parentheses(ast, expr);
+        match &ast.query_expr(expr).kind {
+            ExprKind::Number(_) | ExprKind::Float(_) | ExprKind::String(_) => true,
+            ExprKind::Boolean(_) => true,
+            ExprKind::ArrayLiteral(array) => array.elements.is_empty(),
+            _ => self.hoisted.contains_key(&expr),
+        }
+    }
+
+    /// Writes the statements that evaluate every `if`, block and `match` inside `expr` into a
+    /// temporary, ahead of the statement holding `expr`. They run in the enclosing function,
+    /// so a `return`, `break` or `continue` inside them means what it does in Pulse.
+    fn hoist(&mut self, ast: &mut Ast, expr: ID) -> Result<()> {
+        if !self.needs_hoisting(ast, expr) {
+            return Ok(());
+        }
+
+        let expression = ast.query_expr(expr).clone();
+        let name = format!("__pulse_value_{}", expr);
+        match expression.kind {
+            ExprKind::If(_) | ExprKind::Block(_) | ExprKind::Match(_) => {
+                write!(
+                    self.output,
+                    "{} {};\n",
+                    type_to_str(expression.ty.clone()),
+                    name
+                )?;
+                self.write_tail(ast, expr, &Tail::Assign(name.clone()))?;
+            }
+            // The right operand is only evaluated when the left one does not decide the result.
+            ExprKind::Binary(binary)
+                if matches!(
+                    binary.operator.kind,
+                    BinOpKind::LogicalAnd | BinOpKind::LogicalOr
+                ) && self.needs_hoisting(ast, binary.right) =>
+            {
+                self.hoist(ast, binary.left)?;
+                write!(self.output, "bool {} = ", name)?;
+                self.visit_expression(ast, binary.left)?;
+                let negation = match binary.operator.kind {
+                    BinOpKind::LogicalAnd => "",
+                    _ => "!",
+                };
+                write!(self.output, ";\nif ({}{}) {{\n", negation, name)?;
+                self.hoist(ast, binary.right)?;
+                write!(self.output, "{} = ", name)?;
+                self.visit_expression(ast, binary.right)?;
+                write!(self.output, ";\n}}\n")?;
+            }
+            _ => {
+                let operands = self.operands(ast, expr);
+                if self.needs_ordering(ast, expr) {
+                    return self.hoist_in_order(ast, &operands, operands.len() - 1);
+                }
+                return self.hoist_operands(ast, &operands);
+            }
+        }
+        self.hoisted.insert(expr, name);
+
+        Ok(())
+    }
+
+    /// Where `span` starts in the Pulse source, like `main.pulse:3:14`, for runtime errors.
+    fn location(&self, span: &TextSpan) -> String {
+        let start = &span.start;
+        let file = self
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        format!("{}:{}:{}", file, start.line + 1, start.column + 1)
+    }
+
+    /// Hoists operands evaluated in the given order. The operands before the last one that
+    /// needs hoisting are stored in temporaries first, so they still run before it.
+    fn hoist_operands(&mut self, ast: &mut Ast, operands: &[ID]) -> Result<()> {
+        match operands
+            .iter()
+            .rposition(|operand| self.needs_hoisting(ast, *operand))
+        {
+            Some(last) => self.hoist_in_order(ast, operands, last),
+            None => Ok(()),
+        }
+    }
+
+    /// Stores the operands before `last` in temporaries, in order, and hoists `last`.
+    fn hoist_in_order(&mut self, ast: &mut Ast, operands: &[ID], last: usize) -> Result<()> {
+        for operand in &operands[..last] {
+            self.hoist(ast, *operand)?;
+            self.write_temporary(ast, *operand)?;
+        }
+
+        self.hoist(ast, operands[last])
+    }
+
+    /// Evaluates `expr` into a temporary, unless its value cannot change.
+    fn write_temporary(&mut self, ast: &mut Ast, expr: ID) -> Result<()> {
+        if self.is_constant(ast, expr) {
+            return Ok(());
+        }
+
+        let name = format!("__pulse_value_{}", expr);
+        let type_ = ast.query_expr(expr).ty.clone();
+        write!(self.output, "{} {} = ", type_to_str(type_), name)?;
+        self.visit_expression(ast, expr)?;
+        write!(self.output, ";\n")?;
+        self.hoisted.insert(expr, name);
+
+        Ok(())
+    }
+
+    /// Writes an expression statement, hoisting what C++ cannot write inline first.
+    fn write_expression_statement(&mut self, ast: &mut Ast, expr: ID) -> Result<()> {
+        self.hoist(ast, expr)?;
+        self.visit_expression(ast, expr)
+    }
+
+    /// A `match` becomes a chain of `if`s over a reference to the matched value. When it
+    /// produces a value, every arm hands it to `tail`.
+    fn write_match(
+        &mut self,
+        ast: &mut Ast,
+        match_expr: &MatchExpr,
+        expr: ID,
+        tail: Option<&Tail>,
+    ) -> Result<()> {
+        let scrutinee = format!("__pulse_match_{}", expr);
+
+        self.hoist(ast, match_expr.scrutinee)?;
+        write!(self.output, "{{\nauto&& {} = ", scrutinee)?;
+        self.visit_expression(ast, match_expr.scrutinee)?;
+        write!(self.output, ";\n")?;
+
+        for (i, arm) in match_expr.arms.iter().enumerate() {
+            if i != 0 {
+                write!(self.output, " else ")?;
+            }
+
+            match &arm.pattern {
+                Pattern::Wildcard(_) => write!(self.output, "{{\n")?,
+                Pattern::Literal(literal) => {
+                    write!(self.output, "if ({} == ", scrutinee)?;
+                    self.visit_expression(ast, *literal)?;
+                    write!(self.output, ") {{\n")?;
+                }
+                Pattern::Variant(variant) => {
+                    let alternative = format!(
+                        "{}::{}",
+                        mangle(&variant.enum_name.span.literal),
+                        mangle(variant.variant_name())
+                    );
+                    write!(
+                        self.output,
+                        "if (std::holds_alternative<{}>({}.value)) {{\n",
+                        alternative, scrutinee
+                    )?;
+                    for (index, binding) in variant.bindings.iter().enumerate() {
+                        if binding.is_wildcard() {
+                            continue;
+                        }
+                        let var = self.ctx.lookup_var(binding.variable_id).unwrap();
+                        write!(
+                            self.output,
+                            "{} {} = std::get<{}>({}.value)._{};\n",
+                            type_to_str(var.type_.clone()),
+                            variable_name(binding.variable_id, var),
+                            alternative,
+                            scrutinee,
+                            index
+                        )?;
+                    }
+                }
+            }
+
+            self.write_arm_body(ast, arm.body, tail)?;
+            write!(self.output, "}}")?;
+
+            // Arms after a wildcard can never run.
+            if let Pattern::Wildcard(_) = arm.pattern {
+                break;
+            }
+        }
+
+        let has_wildcard = match_expr
+            .arms
+            .iter()
+            .any(|arm| matches!(arm.pattern, Pattern::Wildcard(_)));
+        if tail.is_some() && !has_wildcard {
+            // Every value is covered by an arm, this only tells the C++ compiler so.
+            write!(self.output, " else {{\nstd::abort();\n}}")?;
+        }
+        write!(self.output, "\n}}\n")?;
+
+        Ok(())
+    }
+}
+
+/// Indices evaluated to find the place `target` names, from the outermost array inwards.
+fn place_operands(ast: &Ast, target: ID) -> Vec<ID> {
+    match &ast.query_expr(target).kind {
+        ExprKind::Parenthesized(parenthesized) => place_operands(ast, parenthesized.inner),
+        ExprKind::FieldAccess(access) => place_operands(ast, access.object),
+        ExprKind::Index(index) => {
+            let mut operands = place_operands(ast, index.object);
+            operands.push(index.index);
+            operands
+        }
+        _ => vec![],
+    }
+}
+
+/// An `if`, block or `match` with a value has no C++ expression, `hoist` and `write_tail` write
+/// it as statements before it can be visited as an expression.
+fn expect_hoisted(expr: &Expr, kind: &str) -> Result<()> {
+    if matches!(expr.ty, Type::Void) {
+        return Ok(());
+    }
+
+    Err(Internal(format!(
+        "`{}` with a value was not hoisted out of its expression",
+        kind
+    )))
+}
+
+/// Whether `call` is to an `std::array` function that changes its first argument in place.
+fn mutates_first_argument(ast: &Ast, call: &CallExpr) -> bool {
+    let module = call
+        .scope
+        .and_then(|scope| match &ast.query_expr(scope).kind {
+            ExprKind::ScopedIdentifier { path } => {
+                path.last().map(|token| token.span.literal.clone())
+            }
+            _ => None,
+        });
+
+    module.as_deref() == Some("array") && call.function_name() != "len"
+}
+
+impl ASTWalker for CppCodegen<'_> {
+    fn visit_func_decl(
+        &mut self,
+        ast: &mut Ast,
+        func_decl: &FunctionDeclaration,
+        item_id: ID,
+    ) -> Result<()> {
+        self.write_signature(func_decl.id)?;
+        write!(self.output, " {{\n")?;
+
+        let func = self.ctx.functions.get(&func_decl.id).unwrap();
+        let body = func.body.stmts.clone();
+
+        // The trailing expression of a function with a return type is its return value.
+        if matches!(func.return_type, Type::Void) {
+            for stmt in body {
+                self.visit_statement(ast, stmt)?;
+            }
+        } else {
+            self.write_tail_body(ast, &body, &Tail::Return)?;
+        }
+
         write!(self.output, "\n}}\n")?;
 
         Ok(())
     }
 
-    // TODO: Allow last exprs in the body to be returned
     fn visit_return_statement(
         &mut self,
         ast: &mut Ast,
         return_statement: &ReturnStmt,
     ) -> Result<()> {
         if let Some(return_value) = &return_statement.return_value {
+            self.hoist(ast, *return_value)?;
             write!(self.output, "return ")?;
             self.visit_expression(ast, *return_value)?;
         } else {
@@ -96,38 +738,135 @@ impl ASTWalker for CppCodegen<'_> {
         Ok(())
     }
 
-    fn visit_if_expression(&mut self, ast: &mut Ast, if_expr: &IfExpr, expr: &Expr) -> Result<()> {
-        write!(self.output, "if (")?;
-        self.visit_expression(ast, if_expr.condition)?;
+    fn visit_while_statement(&mut self, ast: &mut Ast, while_statement: &WhileStmt) -> Result<()> {
+        // A condition with hoisted parts is evaluated at the start of every iteration.
+        if self.needs_hoisting(ast, while_statement.condition) {
+            write!(self.output, "while (true) {{\n")?;
+            self.hoist(ast, while_statement.condition)?;
+            write!(self.output, "if (!(")?;
+            self.visit_expression(ast, while_statement.condition)?;
+            write!(self.output, ")) break;\n")?;
+        } else {
+            write!(self.output, "while (")?;
+            self.visit_expression(ast, while_statement.condition)?;
+            write!(self.output, ") {{\n")?;
+        }
 
-        write!(self.output, ") {{\n")?;
-        match if_expr.else_branch.as_ref() {
-            None => {
-                for stmt in if_expr.then_branch.stmts.clone() {
-                    self.visit_statement(ast, stmt)?;
-                }
+        for stmt in while_statement.body.stmts.clone() {
+            self.visit_statement(ast, stmt)?;
+        }
 
-                write!(self.output, "}}\n")?;
-            }
-            Some(else_branch) => {
-                for stmt in if_expr.then_branch.stmts.clone() {
-                    self.visit_statement(ast, stmt)?;
-                }
+        write!(self.output, "}}\n")?;
 
-                write!(self.output, "}} else {{\n")?;
+        Ok(())
+    }
 
-                for stmt in else_branch.body.stmts.clone() {
-                    self.visit_statement(ast, stmt)?;
-                }
+    fn visit_for_statement(
+        &mut self,
+        ast: &mut Ast,
+        for_statement: &ForStmt,
+        stmt: &Stmt,
+    ) -> Result<()> {
+        let var = self
+            .ctx
+            .lookup_var(for_statement.variable_id)
+            .expect("For loop variable was not resolved");
+        let name = variable_name(for_statement.variable_id, var);
+        // The end of the range is evaluated once, like in the interpreter.
+        let end = format!("__pulse_end_{}", for_statement.variable_id);
+        // Cleared instead of stepping past the largest int, see `math::advance`.
+        let more = format!("__pulse_more_{}", for_statement.variable_id);
 
-                write!(self.output, "}}\n")?;
+        let bounds: Vec<ID> = [for_statement.start, for_statement.end]
+            .into_iter()
+            .chain(for_statement.step)
+            .collect();
+        let step = for_statement
+            .step
+            .filter(|step| !constant_int(ast, *step).is_some_and(|step| step > 0));
+        match step {
+            // The step is evaluated once and checked before the loop, like in the interpreter.
+            Some(step) => {
+                self.hoist_in_order(ast, &bounds, bounds.len() - 1)?;
+                self.write_temporary(ast, step)?;
+                write!(self.output, "if (")?;
+                self.visit_expression(ast, step)?;
+                write!(
+                    self.output,
+                    " <= 0) {{\nstd::cerr << \"Runtime error: Step of a for loop must be \
+                     positive, got \" << "
+                )?;
+                self.visit_expression(ast, step)?;
+                write!(
+                    self.output,
+                    " << \" at {}\" << std::endl;\nstd::abort();\n}}\n",
+                    self.location(&ast.query_expr(step).span(ast))
+                )?;
             }
-            _ => unreachable!("Invalid if expression"),
+            None => self.hoist_operands(ast, &bounds)?,
+        }
+        write!(self.output, "for (int {} = ", name)?;
+        self.visit_expression(ast, for_statement.start)?;
+        write!(self.output, ", {} = ", end)?;
+        self.visit_expression(ast, for_statement.end)?;
+
+        let comparison = if for_statement.inclusive { "<=" } else { "<" };
+        write!(
+            self.output,
+            ", {} = 1; {} && {} {} {}; {} = ::math::advance({}, ",
+            more, more, name, comparison, end, more, name
+        )?;
+        match for_statement.step {
+            Some(step) => self.visit_expression(ast, step)?,
+            None => write!(self.output, "1")?,
+        }
+        write!(self.output, ")) {{\n")?;
+
+        for stmt in for_statement.body.stmts.clone() {
+            self.visit_statement(ast, stmt)?;
         }
 
+        write!(self.output, "}}\n")?;
+
         Ok(())
     }
 
+    fn visit_break_statement(
+        &mut self,
+        _ast: &mut Ast,
+        _break_statement: &BreakStmt,
+    ) -> Result<()> {
+        write!(self.output, "break")?;
+
+        Ok(())
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        _ast: &mut Ast,
+        _continue_statement: &ContinueStmt,
+    ) -> Result<()> {
+        write!(self.output, "continue")?;
+
+        Ok(())
+    }
+
+    // An `if` or block that produces a value is written by `hoist` or `write_tail`.
+    fn visit_if_expression(&mut self, ast: &mut Ast, if_expr: &IfExpr, expr: &Expr) -> Result<()> {
+        expect_hoisted(expr, "if")?;
+        self.write_if_statement(ast, if_expr)
+    }
+
+    fn visit_block_expr(
+        &mut self,
+        ast: &mut Ast,
+        block_expr: &BlockExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        expect_hoisted(expr, "block")?;
+        self.write_block_statement(ast, block_expr)
+    }
+
     fn visit_let_statement(
         &mut self,
         ast: &mut Ast,
@@ -136,19 +875,59 @@ impl ASTWalker for CppCodegen<'_> {
     ) -> Result<()> {
         if let Some(var) = self.ctx.lookup_var(let_statement.variable_id) {
             let type_name = type_to_str(var.type_.clone());
+            let name = variable_name(let_statement.variable_id, var);
 
-            write!(self.output, "{} {} = ", type_name, var.name)?;
+            let global = self
+                .ctx
+                .global_variables
+                .contains(&let_statement.variable_id);
 
-            self.visit_expression(ast, let_statement.initializer)?;
+            // Branches assign the declared variable directly, which keeps `return`, `break`
+            // and `continue` inside them working.
+            let initializer = strip_parentheses(ast, let_statement.initializer);
+            if let ExprKind::Block(_)
+            | ExprKind::Match(_)
+            | ExprKind::If(IfExpr {
+                else_branch: Some(_),
+                ..
+            }) = &ast.query_expr(initializer).kind
+            {
+                if !global {
+                    write!(self.output, "{} {};\n", type_name, name)?;
+                }
+                return self.write_tail(ast, initializer, &Tail::Assign(name));
+            }
+
+            self.hoist(ast, let_statement.initializer)?;
+            // A top-level variable is declared at file scope already, see `write_globals`.
+            if global {
+                write!(self.output, "{} = ", name)?;
+                return self.visit_expression(ast, let_statement.initializer);
+            }
 
-            write!(self.output, "")?;
+            write!(self.output, "{} {} = ", type_name, name)?;
+
+            self.visit_expression(ast, let_statement.initializer)?;
         }
 
         Ok(())
     }
 
     fn visit_statement(&mut self, ast: &mut Ast, statement: ID) -> Result<()> {
-        self.do_visit_statement(ast, statement)?;
+        // A value in statement position is discarded, it needs no temporary.
+        let expr = match ast.query_stmt(statement).kind {
+            StmtKind::Expr(expr) => Some(expr),
+            _ => None,
+        };
+        match expr.map(|expr| (expr, ast.query_expr(expr).kind.clone())) {
+            Some((_, ExprKind::If(if_expr))) => self.write_if_statement(ast, &if_expr)?,
+            Some((_, ExprKind::Block(block))) => self.write_block_statement(ast, &block)?,
+            Some((expr, ExprKind::Match(match_expr))) => {
+                self.write_match(ast, &match_expr, expr, None)?
+            }
+            Some((expr, _)) => self.write_expression_statement(ast, expr)?,
+            None => self.do_visit_statement(ast, statement)?,
+        }
         write!(self.output, ";\n")?;
 
         Ok(())
@@ -166,7 +945,10 @@ impl ASTWalker for CppCodegen<'_> {
             let expr = match &scope.kind {
                 ExprKind::ScopedIdentifier { path } => {
                     for token in path {
+                        // Qualified from the global namespace, `array` alone is ambiguous
+                        // with `std::array`.
                         if token.span.literal == "std" {
+                            write!(self.output, "::")?;
                             continue;
                         }
                         write!(self.output, "{}::", token.span.literal.clone())?;
@@ -214,7 +996,7 @@ impl ASTWalker for CppCodegen<'_> {
 
         if let Some(func) = func {
             let func = self.ctx.functions.get(&func).unwrap();
-            write!(self.output, "{}(", func.name)?;
+            write!(self.output, "{}(", mangle(&func.name))?;
 
             for (i, arg) in call_expression.arguments.iter().enumerate() {
                 if i != 0 {
@@ -230,14 +1012,169 @@ impl ASTWalker for CppCodegen<'_> {
         Ok(())
     }
 
+    fn visit_assignment_expression(
+        &mut self,
+        ast: &mut Ast,
+        assignment_expression: &AssignExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let id = assignment_expression.variable_idx;
+        if let Some(var) = self.ctx.variables.get(&id) {
+            write!(self.output, "{} = ", variable_name(id, var))?;
+        }
+        self.visit_expression(ast, assignment_expression.expression)?;
+
+        Ok(())
+    }
+
+    fn visit_struct_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        struct_literal: &StructLiteralExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let structure = self
+            .ctx
+            .get_struct(struct_literal.struct_name())
+            .cloned()
+            .expect("Struct was not resolved by the type analyzer");
+
+        // Aggregate initialization takes the values in declaration order.
+        write!(self.output, "{}{{", mangle(&structure.name))?;
+        for (i, field) in structure.fields.iter().enumerate() {
+            if i != 0 {
+                write!(self.output, ", ")?;
+            }
+
+            let initializer = struct_literal
+                .fields
+                .iter()
+                .find(|initializer| initializer.identifier.span.literal == field.name)
+                .expect("Missing field was not reported by the type analyzer");
+            self.visit_expression(ast, initializer.value)?;
+        }
+        write!(self.output, "}}")?;
+
+        Ok(())
+    }
+
+    fn visit_field_access_expression(
+        &mut self,
+        ast: &mut Ast,
+        field_access: &FieldAccessExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, field_access.object)?;
+        write!(self.output, ".{}", mangle(field_access.field_name()))?;
+
+        Ok(())
+    }
+
+    fn visit_array_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        array_literal: &ArrayLiteralExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        // `[]` takes its type from where it is used, like an empty initializer list.
+        if !expr.ty.is_unresolved() {
+            write!(self.output, "{}", type_to_str(expr.ty.clone()))?;
+        }
+        write!(self.output, "{{")?;
+        for (i, element) in array_literal.elements.iter().enumerate() {
+            if i != 0 {
+                write!(self.output, ", ")?;
+            }
+            self.visit_expression(ast, *element)?;
+        }
+        write!(self.output, "}}")?;
+
+        Ok(())
+    }
+
+    fn visit_index_expression(
+        &mut self,
+        ast: &mut Ast,
+        index_expression: &IndexExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        // The location is reported by the bounds check in debug builds.
+        write!(self.output, "::array::at(")?;
+        self.visit_expression(ast, index_expression.object)?;
+        write!(self.output, ", ")?;
+        self.visit_expression(ast, index_expression.index)?;
+        write!(self.output, ", \"{}\")", self.location(&expr.span(ast)))?;
+
+        Ok(())
+    }
+
+    fn visit_place_assignment_expression(
+        &mut self,
+        ast: &mut Ast,
+        place_assignment: &PlaceAssignExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, place_assignment.target)?;
+        write!(self.output, " = ")?;
+        self.visit_expression(ast, place_assignment.expression)?;
+
+        Ok(())
+    }
+
+    fn visit_enum_variant_expression(
+        &mut self,
+        ast: &mut Ast,
+        enum_variant: &EnumVariantExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        write!(
+            self.output,
+            "{}{{{}::{}{{",
+            mangle(enum_variant.enum_name()),
+            mangle(enum_variant.enum_name()),
+            mangle(enum_variant.variant_name())
+        )?;
+        for (i, argument) in enum_variant.arguments.iter().enumerate() {
+            if i != 0 {
+                write!(self.output, ", ")?;
+            }
+
+            self.visit_expression(ast, *argument)?;
+        }
+        write!(self.output, "}}}}")?;
+
+        Ok(())
+    }
+
+    // A `match` that produces a value is written by `hoist` or `write_tail`.
+    fn visit_match_expression(
+        &mut self,
+        ast: &mut Ast,
+        match_expr: &MatchExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        expect_hoisted(expr, "match")?;
+        self.write_match(ast, match_expr, expr.id, None)
+    }
+
+    fn visit_expression(&mut self, ast: &mut Ast, expression: ID) -> Result<()> {
+        if let Some(name) = self.hoisted.get(&expression) {
+            write!(self.output, "{}", name)?;
+            return Ok(());
+        }
+
+        self.do_visit_expression(ast, expression)
+    }
+
     fn visit_variable_expression(
         &mut self,
         ast: &mut Ast,
         variable_expression: &VarExpr,
         expr: &Expr,
     ) -> Result<()> {
-        if let Some(var) = self.ctx.variables.get(&variable_expression.variable_idx) {
-            write!(self.output, "{}", var.name)?;
+        let id = variable_expression.variable_idx;
+        if let Some(var) = self.ctx.variables.get(&id) {
+            write!(self.output, "{}", variable_name(id, var))?;
         }
 
         Ok(())
@@ -254,13 +1191,48 @@ impl ASTWalker for CppCodegen<'_> {
         Ok(())
     }
 
+    fn visit_float_expression(
+        &mut self,
+        _ast: &mut Ast,
+        number: &FloatExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        // Pulse float literals are valid C++ double literals as written.
+        write!(self.output, "{}", number.token.span.literal)?;
+
+        Ok(())
+    }
+
+    fn visit_cast_expression(&mut self, ast: &mut Ast, cast: &CastExpr, expr: &Expr) -> Result<()> {
+        write!(
+            self.output,
+            "static_cast<{}>(",
+            type_to_str(expr.ty.clone())
+        )?;
+        self.visit_expression(ast, cast.expression)?;
+        write!(self.output, ")")?;
+
+        Ok(())
+    }
+
     fn visit_string_expression(
         &mut self,
         ast: &mut Ast,
         string: &StringExpr,
         expr: &Expr,
     ) -> Result<()> {
-        write!(self.output, "\"{}\"", string.string)?;
+        let literal = escape_string(&string.string);
+        // A NUL byte would end a plain literal early, so pass the length explicitly.
+        if string.string.contains('\0') {
+            write!(
+                self.output,
+                "std::string(\"{}\", {})",
+                literal,
+                string.string.len()
+            )?;
+        } else {
+            write!(self.output, "\"{}\"", literal)?;
+        }
 
         Ok(())
     }
@@ -286,20 +1258,81 @@ impl ASTWalker for CppCodegen<'_> {
         unary_expression: &UnaryExpr,
         expr: &Expr,
     ) -> Result<()> {
-        todo!()
+        let operator = match unary_expression.operator.kind {
+            UnOpKind::Minus => "-",
+            UnOpKind::BitwiseNot => "~",
+            UnOpKind::LogicalNot => "!",
+        };
+
+        // The operand is always parenthesized, so `-(a + b)` keeps its grouping and `- -x`
+        // never turns into the decrement operator.
+        let operand = strip_parentheses(ast, unary_expression.operand);
+        write!(self.output, "{}(", operator)?;
+        self.visit_expression(ast, operand)?;
+        write!(self.output, ")")?;
+
+        Ok(())
+    }
+
+    fn visit_parenthesized_expression(
+        &mut self,
+        ast: &mut Ast,
+        parenthesized_expression: &ParenthesizedExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        write!(self.output, "(")?;
+        self.visit_expression(ast, parenthesized_expression.inner)?;
+        write!(self.output, ")")?;
+
+        Ok(())
     }
 
     fn visit_binary_expression(
         &mut self,
         ast: &mut Ast,
         binary_expression: &BinaryExpr,
-        _expr: &Expr,
+        expr: &Expr,
     ) -> Result<()> {
-        let str_op = to_operator(binary_expression.operator.clone());
+        let operator = &binary_expression.operator;
+        if matches!(operator.kind, BinOpKind::Power) {
+            if let Some(value) = constant_int(ast, expr.id) {
+                write!(self.output, "{}", value)?;
+                return Ok(());
+            }
+
+            write!(self.output, "::math::ipow(")?;
+            self.visit_expression(ast, binary_expression.left)?;
+            write!(self.output, ", ")?;
+            self.visit_expression(ast, binary_expression.right)?;
+            write!(self.output, ")")?;
+            return Ok(());
+        }
+        // Integer division reports a division by zero at the operator, like the interpreter.
+        if is_runtime_call(ast, binary_expression) {
+            let function = match operator.kind {
+                BinOpKind::Divide => "idiv",
+                _ => "irem",
+            };
+            write!(self.output, "::math::{}(", function)?;
+            self.visit_expression(ast, binary_expression.left)?;
+            write!(self.output, ", ")?;
+            self.visit_expression(ast, binary_expression.right)?;
+            write!(
+                self.output,
+                ", \"{}\")",
+                self.location(&operator.token.span)
+            )?;
+            return Ok(());
+        }
+
+        let precedence = cpp_binary_precedence(&operator.kind);
+        // The tree already holds the Pulse grouping, an operand at the same precedence only
+        // needs parentheses on the side the operator does not associate towards.
+        let right_associative = operator.associativity() == BinOpAssociativity::Right;
 
-        self.visit_expression(ast, binary_expression.left)?;
-        write!(self.output, " {} ", str_op)?;
-        self.visit_expression(ast, binary_expression.right)?;
+        self.write_operand(ast, binary_expression.left, precedence, right_associative)?;
+        write!(self.output, " {} ", to_operator(operator.clone()))?;
+        self.write_operand(ast, binary_expression.right, precedence, !right_associative)?;
 
         Ok(())
     }
@@ -1,10 +1,15 @@
 use crate::ast::span::TextSpan;
-use colored::Colorize;
+use crate::error::error::Error;
+use crate::error::warning::Warning;
+use crate::Result;
+use colored::{ColoredString, Colorize};
 use log::Level;
 use std::io::{BufWriter, Stderr, Write};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Diagnostic {
+    /// Code of the `Error` it comes from, like `P0009`. Warnings have none.
+    pub code: Option<&'static str>,
     pub title: String,
     pub text: Option<String>,
     pub level: Level,
@@ -13,12 +18,92 @@ pub struct Diagnostic {
     pub content: Option<String>,
 }
 
+/// Collects the errors of every stage, so one run reports all of them instead of stopping at
+/// the first.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn report(&mut self, error: Error) {
+        self.diagnostics.extend(error.into_diagnostics());
+    }
+
+    pub fn warn(&mut self, warning: Warning) {
+        self.diagnostics.push(warning.into_diagnostic());
+    }
+
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.diagnostics.extend(other.diagnostics);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Turns every warning into an error.
+    pub fn deny_warnings(&mut self) {
+        for diagnostic in &mut self.diagnostics {
+            if diagnostic.level == Level::Warn {
+                diagnostic.level = Level::Error;
+            }
+        }
+    }
+
+    /// Fails with everything reported, sorted by location, if anything was.
+    pub fn into_result(self) -> Result<()> {
+        if self.diagnostics.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::Diagnostics(self.sorted()))
+    }
+
+    pub fn log_pretty(self, buff: &mut BufWriter<Stderr>) {
+        for diagnostic in self.sorted() {
+            diagnostic.log_pretty(buff);
+        }
+    }
+
+    fn sorted(mut self) -> Vec<Diagnostic> {
+        self.diagnostics.sort_by_key(|diagnostic| {
+            diagnostic
+                .location
+                .as_ref()
+                .map(|location| (location.start.line, location.start.column))
+        });
+
+        self.diagnostics
+    }
+}
+
 impl Diagnostic {
+    /// Warnings are yellow, everything else is red.
+    fn colorize(&self, text: &str) -> ColoredString {
+        match self.level {
+            Level::Warn => text.yellow(),
+            _ => text.bright_red(),
+        }
+    }
+
     pub fn log_pretty(&self, buff: &mut BufWriter<Stderr>) {
+        let label = match self.level {
+            Level::Warn => "warning".to_string(),
+            level => level.to_string().to_lowercase(),
+        };
+        let header = match self.code {
+            Some(code) => format!("{}[{}]", label, code),
+            None => label,
+        };
         writeln!(
             buff,
             "{}{}{}",
-            self.level.to_string().to_lowercase().bright_red(),
+            self.colorize(&header),
             ": ".dimmed(),
             self.title
         )
@@ -26,10 +111,11 @@ impl Diagnostic {
 
         if let Some(location) = &self.location {
             if let Some(content) = &self.content {
+                // The end of input is past the last line when the source ends with a newline.
                 let line = content
                     .lines()
                     .nth(location.start.line as usize)
-                    .expect("Error getting line");
+                    .unwrap_or_default();
                 let line_number = location.start.line;
                 let column = location.start.column;
                 let line_content = line.trim_end();
@@ -50,7 +136,7 @@ impl Diagnostic {
 
                 let padding_left =
                     " ".repeat((column + 6 + line_number.to_string().len() as u32) as usize);
-                writeln!(buff, "{}{}", padding_left, decoration.bright_red())
+                writeln!(buff, "{}{}", padding_left, self.colorize(&decoration))
                     .expect("Error writing decoration");
 
                 if line_number > 1 {
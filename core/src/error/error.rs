@@ -6,12 +6,8 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("{0}")]
-    Generic(String, Option<String>),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-    #[error("{0} is not implemented")]
-    NotImplemented(String),
     #[error("Invalid extension provided: {0}")]
     InvalidExtension(String),
     #[error("Provided file does not exist")]
@@ -22,16 +18,20 @@ pub enum Error {
     InvalidType(String, TextSpan, String),
     #[error("Function {0} already exists")]
     FunctionAlreadyExists(String, TextSpan, String),
-    #[error("Main function cannot have parameters")]
-    MainFunctionParameters,
+    #[error("Invalid signature for the main function")]
+    MainFunctionParameters(TextSpan, String),
+    #[error("No main function found")]
+    MissingMain,
+    #[error("Found {} errors", .0.len())]
+    Diagnostics(Vec<Diagnostic>),
     #[error("Type mismatch. Attempted to assign {0} to {1}")]
     TypeMismatch(String, String, TextSpan, String),
     #[error("Cannot find {0} in the current scope")]
-    NotFound(String, TextSpan, String),
+    NotFound(String, Option<String>, TextSpan, String),
     #[error("Illegal return statement")]
     IllegalReturn(TextSpan, String),
     #[error("Tried to call undeclared function: {0}")]
-    CallToUndeclaredFunction(String, TextSpan, String),
+    CallToUndeclaredFunction(String, Option<String>, TextSpan, String),
     #[error("Invalid arguments provided to call expresion. Expected {0}, got {1}")]
     InvalidArguments(usize, usize, TextSpan, String),
     #[error("Tried to create function with std reserved name: {0}")]
@@ -42,46 +42,122 @@ pub enum Error {
     CompilerNotFound(String),
     #[error("Couldn't find program: {0}")]
     WhichError(#[from] which::Error),
-}
-
-impl From<String> for Error {
-    fn from(s: String) -> Self {
-        Self::Generic(s, None)
-    }
+    #[error("Runtime error: {0}")]
+    RuntimeError(String, TextSpan, String),
+    #[error("Unterminated block comment")]
+    UnterminatedComment(TextSpan, String),
+    #[error("Unterminated string literal")]
+    UnterminatedString(TextSpan, String),
+    #[error("Invalid escape sequence {0}")]
+    InvalidEscape(String, TextSpan, String),
+    #[error("Integer literal {0} is out of range")]
+    IntegerOutOfRange(String, TextSpan, String),
+    #[error("`{0}` used outside of a loop")]
+    OutsideLoop(String, TextSpan, String),
+    #[error("Type {0} already exists")]
+    TypeAlreadyExists(String, TextSpan, String),
+    #[error("Struct {1} has no field named {0}")]
+    UnknownField(String, String, TextSpan, String),
+    #[error("Missing fields in initializer of {0}: {1}")]
+    MissingFields(String, String, TextSpan, String),
+    #[error("Invalid left-hand side of assignment")]
+    InvalidAssignmentTarget(TextSpan, String),
+    #[error("Type {0} contains itself")]
+    RecursiveType(String, TextSpan, String),
+    #[error("Enum {1} has no variant named {0}")]
+    UnknownVariant(String, String, TextSpan, String),
+    #[error("Match is not exhaustive, missing {0}")]
+    NonExhaustiveMatch(String, TextSpan, String),
+    #[error("Function {0} can reach its end without returning {1}")]
+    MissingReturn(String, String, TextSpan, String),
+    #[error("Cannot cast {0} to {1}")]
+    InvalidCast(String, String, TextSpan, String),
+    #[error("Cannot infer the element type of an empty array")]
+    CannotInferType(TextSpan, String),
+    #[error("Internal compiler error: {0}")]
+    Internal(String),
+    #[error("Statements outside of a function are not allowed next to `fn main`")]
+    StatementOutsideMain(TextSpan, String),
+    #[error("{1} uses the top-level variable {0} before it is initialized")]
+    UninitializedGlobal(String, String, TextSpan, String),
+    #[error("Unknown error code {0}")]
+    UnknownErrorCode(String),
 }
 
 impl Error {
     pub fn io(err: std::io::Error) -> Self {
         Self::Io(err)
     }
-
-    pub fn generic(title: &str, msg: Option<&str>) -> Self {
-        Self::Generic(title.to_string(), msg.map(|s| s.to_string()))
-    }
 }
 
 impl Error {
+    /// Stable identifier of the error, printed in its header and explained by `pulse explain`.
+    /// A new variant takes the next free code, codes are never reused or renumbered.
+    /// `Diagnostics` only carries the errors it collected, each with its own code.
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            Self::Diagnostics(..) => None,
+            Self::Io(..) => Some("P0001"),
+            Self::InvalidExtension(..) => Some("P0002"),
+            Self::FileDoesNotExist => Some("P0003"),
+            Self::ParseError(..) => Some("P0004"),
+            Self::InvalidType(..) => Some("P0005"),
+            Self::FunctionAlreadyExists(..) => Some("P0006"),
+            Self::MainFunctionParameters(..) => Some("P0007"),
+            Self::MissingMain => Some("P0008"),
+            Self::TypeMismatch(..) => Some("P0009"),
+            Self::NotFound(..) => Some("P0010"),
+            Self::IllegalReturn(..) => Some("P0011"),
+            Self::CallToUndeclaredFunction(..) => Some("P0012"),
+            Self::InvalidArguments(..) => Some("P0013"),
+            Self::ReservedName(..) => Some("P0014"),
+            Self::FormatError(..) => Some("P0015"),
+            Self::CompilerNotFound(..) => Some("P0016"),
+            Self::WhichError(..) => Some("P0017"),
+            Self::RuntimeError(..) => Some("P0018"),
+            Self::UnterminatedComment(..) => Some("P0019"),
+            Self::UnterminatedString(..) => Some("P0020"),
+            Self::InvalidEscape(..) => Some("P0021"),
+            Self::IntegerOutOfRange(..) => Some("P0022"),
+            Self::OutsideLoop(..) => Some("P0023"),
+            Self::TypeAlreadyExists(..) => Some("P0024"),
+            Self::UnknownField(..) => Some("P0025"),
+            Self::MissingFields(..) => Some("P0026"),
+            Self::InvalidAssignmentTarget(..) => Some("P0027"),
+            Self::RecursiveType(..) => Some("P0028"),
+            Self::UnknownVariant(..) => Some("P0029"),
+            Self::NonExhaustiveMatch(..) => Some("P0030"),
+            Self::MissingReturn(..) => Some("P0031"),
+            Self::InvalidCast(..) => Some("P0032"),
+            Self::CannotInferType(..) => Some("P0033"),
+            Self::Internal(..) => Some("P0034"),
+            Self::StatementOutsideMain(..) => Some("P0035"),
+            Self::UninitializedGlobal(..) => Some("P0036"),
+            Self::UnknownErrorCode(..) => Some("P0037"),
+        }
+    }
+
     pub fn log_pretty(self, buffer: &mut BufWriter<Stderr>) {
-        let diagnostic = self.into_diagnostic();
+        for diagnostic in self.into_diagnostics() {
+            diagnostic.log_pretty(buffer);
+        }
+    }
 
-        diagnostic.log_pretty(buffer);
+    /// Every diagnostic of the error, errors collected by `Diagnostics` are logged one by one.
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        match self {
+            Self::Diagnostics(diagnostics) => diagnostics,
+            error => vec![error.into_diagnostic()],
+        }
     }
 
     pub fn into_diagnostic(self) -> Diagnostic {
         let string = self.to_string();
+        let code = self.code();
 
         let (title, text, level, location, hint, content) = match self {
-            Self::Generic(title, msg) => (title, msg, Level::Error, None, None, None),
             Self::Io(msg)   => (msg.to_string(), None, Level::Error, None, None, None),
             Self::FormatError(msg) => (msg.to_string(), None, Level::Error, None, None, None),
-            Self::NotImplemented(_) => (
-                self.to_string(),
-                None,
-                Level::Error,
-                None,
-                Some("Check if there is an issue open for it on the GitHub.".to_string()),
-                None,
-            ),
             Self::InvalidExtension(_) => (
                 self.to_string(),
                 Some("Expected file extension to be `.pulse`".to_string()),
@@ -112,20 +188,141 @@ impl Error {
             }
             Self::FunctionAlreadyExists(_, span, content)
             | Self::InvalidType(_, span, content)
-            | Self::NotFound(_, span, content)
-            | Self::CallToUndeclaredFunction(_, span, content)
             | Self::IllegalReturn(span, content)
             | Self::TypeMismatch(_, _, span, content)
             | Self::InvalidArguments(_, _, span, content)
-            | Self::ReservedName(_, span, content) => {
+            | Self::ReservedName(_, span, content)
+            | Self::RuntimeError(_, span, content)
+            | Self::OutsideLoop(_, span, content)
+            | Self::TypeAlreadyExists(_, span, content)
+            | Self::UnknownField(_, _, span, content)
+            | Self::MissingFields(_, _, span, content)
+            | Self::InvalidAssignmentTarget(span, content)
+            | Self::RecursiveType(_, span, content)
+            | Self::UnknownVariant(_, _, span, content)
+            | Self::NonExhaustiveMatch(_, span, content)
+            | Self::InvalidCast(_, _, span, content) => {
                 (string, None, Level::Error, Some(span), None, Some(content))
             }
-            Self::MainFunctionParameters => {
-                (self.to_string(), None, Level::Error, None, None, None)
-            }
+            Self::NotFound(_, suggestion, span, content)
+            | Self::CallToUndeclaredFunction(_, suggestion, span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                suggestion.map(|suggestion| format!("Did you mean `{}`?", suggestion)),
+                Some(content),
+            ),
+            Self::UnterminatedComment(span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some("Close the comment with `*/`.".to_string()),
+                Some(content),
+            ),
+            Self::MissingReturn(_, _, span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some("Add a `return`, or end the body with an expression of the return type.".to_string()),
+                Some(content),
+            ),
+            Self::UnterminatedString(span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some("Close the string with `\"`.".to_string()),
+                Some(content),
+            ),
+            Self::InvalidEscape(_, span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some(
+                    "Supported escapes are `\\n`, `\\t`, `\\\\`, `\\\"` and `\\u{...}`."
+                        .to_string(),
+                ),
+                Some(content),
+            ),
+            Self::IntegerOutOfRange(_, span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some(format!("Integers are 32 bits, the largest literal is {}.", i32::MAX)),
+                Some(content),
+            ),
+            Self::CannotInferType(span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some("Add a type annotation, for example `let values: [int] = [];`.".to_string()),
+                Some(content),
+            ),
+            Self::StatementOutsideMain(span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some("Move it into `main`, or remove `fn main` to run the file as a script.".to_string()),
+                Some(content),
+            ),
+            Self::UninitializedGlobal(name, _, span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some(format!("Top-level variables are initialized in order, move `let {}` above this call.", name)),
+                Some(content),
+            ),
+            Self::MainFunctionParameters(span, content) => (
+                string,
+                None,
+                Level::Error,
+                Some(span),
+                Some(
+                    "Declare it as `fn main()`, `fn main() -> int` or `fn main(args: [string]) -> int`."
+                        .to_string(),
+                ),
+                Some(content),
+            ),
+            Self::UnknownErrorCode(_) => (
+                string,
+                None,
+                Level::Error,
+                None,
+                Some("Codes are printed in the header of each error, like `error[P0009]`.".to_string()),
+                None,
+            ),
+            Self::Internal(_) => (
+                string,
+                None,
+                Level::Error,
+                None,
+                Some("This is a bug in the compiler, please report it with the program that caused it.".to_string()),
+                None,
+            ),
+            Self::Diagnostics(_) => (string, None, Level::Error, None, None, None),
+            Self::MissingMain => (
+                self.to_string(),
+                None,
+                Level::Error,
+                None,
+                Some(
+                    "Add `fn main() { ... }`, or write statements outside of functions to run the file as a script."
+                        .to_string(),
+                ),
+                None,
+            ),
         };
 
         Diagnostic {
+            code,
             title,
             text,
             level,
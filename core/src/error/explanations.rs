@@ -0,0 +1,700 @@
+/// Extended explanation of an error code, as printed by `pulse explain`.
+pub fn explain(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| explanation.trim())
+}
+
+/// One entry per code of `Error::code`. Errors caused by the program come with an example of
+/// the mistake and the example fixed.
+const EXPLANATIONS: &[(&str, &str)] = &[
+    (
+        "P0001",
+        r#"
+Reading or writing a file failed, usually because of a missing directory or permissions.
+
+Erroneous example, the build directory cannot be created in a read-only folder:
+
+    cd /usr && pulse run ~/project/main.pulse
+
+Run from a directory you can write to, `build/` is created next to where `pulse` runs:
+
+    cd ~/project && pulse run main.pulse
+"#,
+    ),
+    (
+        "P0002",
+        r#"
+`pulse run` only accepts files with the `.pulse` extension.
+
+Erroneous example:
+
+    pulse run main.txt
+
+Rename the file:
+
+    mv main.txt main.pulse
+    pulse run main.pulse
+"#,
+    ),
+    (
+        "P0003",
+        r#"
+The file passed to `pulse run` does not exist. Relative paths start at the current
+directory.
+
+Erroneous example:
+
+    pulse run mian.pulse
+
+Fixed:
+
+    pulse run main.pulse
+"#,
+    ),
+    (
+        "P0004",
+        r#"
+The source is not valid Pulse syntax. The message names the token that was expected and
+the one that was found.
+
+Erroneous example:
+
+    fn main() {
+        println(1 2);
+    }
+
+Separate the arguments with a comma:
+
+    fn main() {
+        println(1, 2);
+    }
+"#,
+    ),
+    (
+        "P0005",
+        r#"
+A type annotation names a type that does not exist. Built-in types are `int`, `float`,
+`bool`, `string` and arrays like `[int]`, any other name must be a declared struct or enum.
+
+Erroneous example:
+
+    fn area(width: integer) -> int {
+        width * width
+    }
+
+Fixed:
+
+    fn area(width: int) -> int {
+        width * width
+    }
+"#,
+    ),
+    (
+        "P0006",
+        r#"
+Two functions have the same name. Functions cannot be overloaded.
+
+Erroneous example:
+
+    fn area(width: int) -> int { width * width }
+    fn area(width: int, height: int) -> int { width * height }
+
+Give each function its own name:
+
+    fn square_area(width: int) -> int { width * width }
+    fn area(width: int, height: int) -> int { width * height }
+"#,
+    ),
+    (
+        "P0007",
+        r#"
+`main` is declared with a signature the program cannot be started with. It takes no
+parameters or the command line arguments, and returns nothing or the exit status.
+
+Erroneous example:
+
+    fn main(count: int) -> bool {
+        true
+    }
+
+One of the valid forms:
+
+    fn main(args: [string]) -> int {
+        std::array::len(args)
+    }
+"#,
+    ),
+    (
+        "P0008",
+        r#"
+The file declares functions but no `main`, so there is nothing to run.
+
+Erroneous example:
+
+    fn greet() {
+        println("Hello");
+    }
+
+Add a `main`:
+
+    fn greet() {
+        println("Hello");
+    }
+
+    fn main() {
+        greet();
+    }
+
+Or write the statements at the top level to run the file as a script:
+
+    println("Hello");
+"#,
+    ),
+    (
+        "P0009",
+        r#"
+A value has a different type than the one expected where it is used, for example in an
+annotated `let`, as an argument, as an operand or as a condition.
+
+Erroneous example:
+
+    fn main() {
+        let count: int = "three";
+    }
+
+Fixed:
+
+    fn main() {
+        let count: int = 3;
+    }
+"#,
+    ),
+    (
+        "P0010",
+        r#"
+A name is used that no variable in scope has. Variables are visible from their `let` to
+the end of the enclosing block.
+
+Erroneous example:
+
+    fn main() {
+        if true {
+            let total = 1;
+        }
+        println(total);
+    }
+
+Declare the variable where it is used:
+
+    fn main() {
+        let total = 1;
+        println(total);
+    }
+"#,
+    ),
+    (
+        "P0011",
+        r#"
+`return` is used outside of a function, for example at the top level of a script.
+
+Erroneous example:
+
+    println("done");
+    return;
+
+Let the script end, or move the code into a function:
+
+    println("done");
+"#,
+    ),
+    (
+        "P0012",
+        r#"
+A function is called that is not declared. Functions of the standard library are called
+with their module, like `std::math::sqrt`.
+
+Erroneous example:
+
+    fn main() {
+        let root = sqrt(2.0);
+    }
+
+Fixed:
+
+    fn main() {
+        let root = std::math::sqrt(2.0);
+    }
+"#,
+    ),
+    (
+        "P0013",
+        r#"
+A function is called with a different number of arguments than it has parameters.
+
+Erroneous example:
+
+    fn add(a: int, b: int) -> int { a + b }
+
+    fn main() {
+        println(add(1));
+    }
+
+Fixed:
+
+    fn add(a: int, b: int) -> int { a + b }
+
+    fn main() {
+        println(add(1, 2));
+    }
+"#,
+    ),
+    (
+        "P0014",
+        r#"
+A function is declared with the name of a printing function of the standard library,
+`print`, `println`, `eprint` or `eprintln`.
+
+Erroneous example:
+
+    fn println(message: string) {}
+
+Choose another name:
+
+    fn log(message: string) {
+        println(message);
+    }
+"#,
+    ),
+    (
+        "P0015",
+        r#"
+Writing the generated C++ failed. This is a bug in the compiler, please report it with the
+program that caused it.
+"#,
+    ),
+    (
+        "P0016",
+        r#"
+`pulse run` compiles the generated C++ with a C++ compiler and none was found. It looks
+for `g++` or `clang++`, and for MSVC on Windows.
+
+Install one of them, or run the program with the interpreter instead:
+
+    pulse run --interpret main.pulse
+"#,
+    ),
+    (
+        "P0017",
+        r#"
+A program `pulse` runs, like the C++ compiler, could not be found in the `PATH`.
+
+Erroneous example, `g++` is installed in a directory missing from the `PATH`:
+
+    PATH=/usr/local/bin pulse run main.pulse
+
+Add its directory to the `PATH`:
+
+    PATH=/usr/local/bin:/usr/bin pulse run main.pulse
+"#,
+    ),
+    (
+        "P0018",
+        r#"
+The interpreter stopped the program because an operation cannot be done, like an array
+index out of bounds or a division by zero.
+
+Erroneous example:
+
+    fn main() {
+        let values = [1, 2, 3];
+        println(values[3]);
+    }
+
+Stay within the length of the array:
+
+    fn main() {
+        let values = [1, 2, 3];
+        println(values[std::array::len(values) - 1]);
+    }
+"#,
+    ),
+    (
+        "P0019",
+        r#"
+A block comment is not closed before the end of the file.
+
+Erroneous example:
+
+    /* Adds two numbers.
+    fn add(a: int, b: int) -> int { a + b }
+
+Fixed:
+
+    /* Adds two numbers. */
+    fn add(a: int, b: int) -> int { a + b }
+"#,
+    ),
+    (
+        "P0020",
+        r#"
+A string literal is not closed before the end of the line.
+
+Erroneous example:
+
+    fn main() {
+        println("Hello);
+    }
+
+Fixed:
+
+    fn main() {
+        println("Hello");
+    }
+"#,
+    ),
+    (
+        "P0021",
+        r#"
+A string contains an escape sequence that does not exist. Supported escapes are `\n`,
+`\t`, `\\`, `\"` and `\u{...}`.
+
+Erroneous example:
+
+    fn main() {
+        println("C:\path");
+    }
+
+Escape the backslash:
+
+    fn main() {
+        println("C:\\path");
+    }
+"#,
+    ),
+    (
+        "P0022",
+        r#"
+An integer literal does not fit in an `int`. Integers are 32 bits, so the largest literal
+is 2147483647. A negative number is the negation of a literal.
+
+Erroneous example:
+
+    fn main() {
+        let big = 3000000000;
+    }
+
+Use a float for larger values:
+
+    fn main() {
+        let big = 3000000000.0;
+    }
+"#,
+    ),
+    (
+        "P0023",
+        r#"
+`break` or `continue` is used outside of a `while` or `for` loop. The condition of a
+`while` loop is outside of it too.
+
+Erroneous example:
+
+    fn main() {
+        if true {
+            break;
+        }
+        while { if true { continue; } false } {}
+    }
+
+Use them inside a loop:
+
+    fn main() {
+        while true {
+            break;
+        }
+    }
+"#,
+    ),
+    (
+        "P0024",
+        r#"
+Two structs or enums have the same name.
+
+Erroneous example:
+
+    struct Point { x: int, y: int }
+    enum Point { Origin }
+
+Fixed:
+
+    struct Point { x: int, y: int }
+    enum Corner { Origin }
+"#,
+    ),
+    (
+        "P0025",
+        r#"
+A struct literal or a field access uses a field the struct does not declare.
+
+Erroneous example:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let point = Point { x: 1, y: 2 };
+        println(point.z);
+    }
+
+Fixed:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let point = Point { x: 1, y: 2 };
+        println(point.y);
+    }
+"#,
+    ),
+    (
+        "P0026",
+        r#"
+A struct literal does not initialize every field of the struct.
+
+Erroneous example:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let point = Point { x: 1 };
+    }
+
+Fixed:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let point = Point { x: 1, y: 0 };
+    }
+"#,
+    ),
+    (
+        "P0027",
+        r#"
+Only variables, fields and array elements can be assigned to.
+
+Erroneous example:
+
+    fn main() {
+        let total = 1;
+        total + 1 = 2;
+    }
+
+Fixed:
+
+    fn main() {
+        let total = 1;
+        total = total + 1;
+    }
+"#,
+    ),
+    (
+        "P0028",
+        r#"
+A struct or enum contains itself, directly or through other types, so its size would be
+infinite. Arrays are allowed, they hold their elements elsewhere.
+
+Erroneous example:
+
+    struct Node { value: int, next: Node }
+
+Fixed:
+
+    struct Node { value: int, children: [Node] }
+"#,
+    ),
+    (
+        "P0029",
+        r#"
+An enum variant is used that the enum does not declare.
+
+Erroneous example:
+
+    enum Color { Red, Green }
+
+    fn main() {
+        let color = Color::Blue;
+    }
+
+Fixed:
+
+    enum Color { Red, Green, Blue }
+
+    fn main() {
+        let color = Color::Blue;
+    }
+"#,
+    ),
+    (
+        "P0030",
+        r#"
+A `match` does not handle every possible value. Add the missing variants, or a `_` arm
+for everything else.
+
+Erroneous example:
+
+    enum Color { Red, Green, Blue }
+
+    fn name(color: Color) -> int {
+        match color {
+            Color::Red => 1,
+            Color::Green => 2,
+        }
+    }
+
+Fixed:
+
+    enum Color { Red, Green, Blue }
+
+    fn name(color: Color) -> int {
+        match color {
+            Color::Red => 1,
+            _ => 2,
+        }
+    }
+"#,
+    ),
+    (
+        "P0031",
+        r#"
+A function with a return type can reach the end of its body without a value. The body has
+to end with an expression of the return type, or return on every path.
+
+Erroneous example:
+
+    fn sign(value: int) -> int {
+        if value < 0 {
+            return -1;
+        }
+    }
+
+Fixed:
+
+    fn sign(value: int) -> int {
+        if value < 0 {
+            return -1;
+        }
+        1
+    }
+"#,
+    ),
+    (
+        "P0032",
+        r#"
+`as` only converts between `int` and `float`.
+
+Erroneous example:
+
+    fn main() {
+        let count = "3" as int;
+    }
+
+Fixed:
+
+    fn main() {
+        let count = 3.0 as int;
+    }
+"#,
+    ),
+    (
+        "P0033",
+        r#"
+The type of a variable cannot be inferred from its initializer, like an empty array.
+
+Erroneous example:
+
+    fn main() {
+        let values = [];
+    }
+
+Annotate the type:
+
+    fn main() {
+        let values: [int] = [];
+    }
+"#,
+    ),
+    (
+        "P0034",
+        r#"
+The compiler reached a state it should never be in while generating code. This is a bug in
+the compiler, not in the program.
+
+Please report it together with the program that caused it, ideally reduced to the smallest
+program that still shows the error.
+"#,
+    ),
+    (
+        "P0035",
+        r#"
+A file with `fn main` can only have `let`s, functions and types at the top level. Other
+top-level statements make the file a script, which runs them instead of a `main`.
+
+Erroneous example:
+
+    println("Starting");
+
+    fn main() {
+        println("Running");
+    }
+
+Move the statement into `main`:
+
+    fn main() {
+        println("Starting");
+        println("Running");
+    }
+"#,
+    ),
+    (
+        "P0036",
+        r#"
+A top-level statement calls a function that uses a top-level variable declared at or after
+that statement. Top-level variables are initialized in order, so the variable has no value
+yet when the function runs.
+
+Erroneous example:
+
+    let doubled = double();
+    let base = 5;
+
+    fn double() -> int {
+        base * 2
+    }
+
+Declare the variable before the call:
+
+    let base = 5;
+    let doubled = double();
+
+    fn double() -> int {
+        base * 2
+    }
+"#,
+    ),
+    (
+        "P0037",
+        r#"
+`pulse explain` was given a code that does not exist. Codes are printed in the header of
+each error, like `error[P0009]`.
+
+Erroneous example:
+
+    pulse explain P9999
+
+Fixed:
+
+    pulse explain P0009
+"#,
+    ),
+];
@@ -1,2 +1,4 @@
 pub mod diagnostics;
 pub mod error;
+pub mod explanations;
+pub mod warning;
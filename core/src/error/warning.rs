@@ -0,0 +1,53 @@
+use crate::ast::span::TextSpan;
+use crate::error::diagnostics::Diagnostic;
+use log::Level;
+use thiserror::Error;
+
+/// Code that type checks but is most likely a mistake. Warnings do not stop the build and have
+/// no code, `pulse explain` only covers errors. `--deny-warnings` reports them as errors.
+#[derive(Debug, Error)]
+pub enum Warning {
+    #[error("Unused variable {0}")]
+    UnusedVariable(String, TextSpan, String),
+    #[error("Function {0} is never called")]
+    UnusedFunction(String, TextSpan, String),
+    #[error("Unreachable statement")]
+    UnreachableCode(TextSpan, String),
+    #[error("Loop never runs")]
+    LoopNeverRuns(TextSpan, String),
+}
+
+impl Warning {
+    pub fn into_diagnostic(self) -> Diagnostic {
+        let title = self.to_string();
+
+        let (hint, span, content) = match self {
+            Self::UnusedVariable(_, span, content) => (
+                "Remove it, or start its name with `_` if it is meant to be unused.",
+                span,
+                content,
+            ),
+            Self::UnusedFunction(_, span, content) => (
+                "Only functions reachable from `main` are used.",
+                span,
+                content,
+            ),
+            Self::UnreachableCode(span, content) => {
+                ("It follows a `return`, so it never runs.", span, content)
+            }
+            Self::LoopNeverRuns(span, content) => {
+                ("Its condition is always `false`.", span, content)
+            }
+        };
+
+        Diagnostic {
+            code: None,
+            title,
+            text: None,
+            level: Level::Warn,
+            location: Some(span),
+            hint: Some(hint.to_string()),
+            content: Some(content),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::ast::function::{Body, Function};
+use crate::ast::structure::{Enum, Struct, StructField, Variant};
 use crate::ast::{new_id, ID};
-use crate::error::error::Error::FunctionAlreadyExists;
+use crate::error::error::Error::{FunctionAlreadyExists, TypeAlreadyExists};
 use crate::lexer::token::Token;
 use crate::types::Type;
 use crate::Result;
@@ -15,9 +16,12 @@ pub struct Variable {
 
 #[derive(Debug, Clone)]
 pub struct GlobalContext {
-    pub global_variables: Vec<Variable>,
+    /// Variables declared by top-level statements, in declaration order.
+    pub global_variables: Vec<ID>,
     pub variables: IndexMap<ID, Variable>,
     pub functions: IndexMap<ID, Function>,
+    pub structs: IndexMap<ID, Struct>,
+    pub enums: IndexMap<ID, Enum>,
 }
 
 impl GlobalContext {
@@ -26,6 +30,8 @@ impl GlobalContext {
             global_variables: vec![],
             variables: IndexMap::new(),
             functions: IndexMap::new(),
+            structs: IndexMap::new(),
+            enums: IndexMap::new(),
         }
     }
 
@@ -41,12 +47,7 @@ impl GlobalContext {
         );
 
         if global {
-            self.global_variables.push(
-                self.variables
-                    .get(&id)
-                    .expect("Variable was not inserted into the global context")
-                    .clone(),
-            );
+            self.global_variables.push(id);
         }
 
         id
@@ -57,14 +58,18 @@ impl GlobalContext {
     }
 
     pub fn get_global_variable(&self, name: &str) -> Option<&Variable> {
-        self.global_variables.iter().find(|var| var.name == name)
+        self.lookup_var_id(name)
+            .and_then(|id| self.variables.get(&id))
     }
 
+    /// Finds the top-level variable visible under `identifier`, the latest one when top-level
+    /// statements shadow it.
     pub fn lookup_var_id(&self, identifier: &str) -> Option<ID> {
-        self.variables
+        self.global_variables
             .iter()
-            .find(|(_, var)| var.name == identifier)
-            .map(|(id, _)| *id)
+            .rev()
+            .find(|id| self.variables[*id].name == identifier)
+            .copied()
     }
 
     pub fn lookup_function(&self, identifier: &str) -> Option<ID> {
@@ -106,4 +111,85 @@ impl GlobalContext {
 
         Ok(self.push_function(function))
     }
+
+    pub fn lookup_struct(&self, identifier: &str) -> Option<ID> {
+        self.structs
+            .iter()
+            .find(|(_, structure)| structure.name == identifier)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn get_struct(&self, identifier: &str) -> Option<&Struct> {
+        self.structs
+            .values()
+            .find(|structure| structure.name == identifier)
+    }
+
+    /// Registers a struct name before its fields are known, so types can refer to structs
+    /// declared later in the file.
+    pub fn declare_struct(&mut self, identifier: &Token, content: &String) -> Result<ID> {
+        let name = self.new_type_name(identifier, content)?;
+        let id = new_id(self.structs.len() as u32);
+        self.structs.insert(
+            id,
+            Struct {
+                name,
+                fields: vec![],
+            },
+        );
+        Ok(id)
+    }
+
+    pub fn define_struct_fields(&mut self, id: ID, fields: Vec<StructField>) {
+        if let Some(structure) = self.structs.get_mut(&id) {
+            structure.fields = fields;
+        }
+    }
+
+    pub fn lookup_enum(&self, identifier: &str) -> Option<ID> {
+        self.enums
+            .iter()
+            .find(|(_, enumeration)| enumeration.name == identifier)
+            .map(|(id, _)| *id)
+    }
+
+    pub fn get_enum(&self, identifier: &str) -> Option<&Enum> {
+        self.enums
+            .values()
+            .find(|enumeration| enumeration.name == identifier)
+    }
+
+    /// Registers an enum name before its variants are known, like `declare_struct`.
+    pub fn declare_enum(&mut self, identifier: &Token, content: &String) -> Result<ID> {
+        let name = self.new_type_name(identifier, content)?;
+        let id = new_id(self.enums.len() as u32);
+        self.enums.insert(
+            id,
+            Enum {
+                name,
+                variants: vec![],
+            },
+        );
+        Ok(id)
+    }
+
+    pub fn define_enum_variants(&mut self, id: ID, variants: Vec<Variant>) {
+        if let Some(enumeration) = self.enums.get_mut(&id) {
+            enumeration.variants = variants;
+        }
+    }
+
+    /// Structs and enums share one namespace.
+    fn new_type_name(&self, identifier: &Token, content: &String) -> Result<String> {
+        let name = identifier.span.literal.to_string();
+        if self.lookup_struct(&name).is_some() || self.lookup_enum(&name).is_some() {
+            return Err(TypeAlreadyExists(
+                name,
+                identifier.span.clone(),
+                content.clone(),
+            ));
+        }
+
+        Ok(name)
+    }
 }
@@ -0,0 +1,328 @@
+use crate::ast::expr::ExprKind;
+use crate::ast::item::ItemKind;
+use crate::ast::stmt::StmtKind;
+use crate::ast::visitor::ASTWalker;
+use crate::ast::{Ast, ID};
+use crate::error::error::Error::RuntimeError;
+use crate::global_context::GlobalContext;
+use crate::types::Type;
+use crate::Result;
+use indexmap::IndexMap;
+
+pub mod stdlib;
+pub mod value;
+pub mod walker;
+
+pub use value::Value;
+
+/// A place an assignment writes to, with its indices already evaluated.
+#[derive(Debug, Clone)]
+pub struct Place {
+    variable: ID,
+    steps: Vec<PlaceStep>,
+}
+
+#[derive(Debug, Clone)]
+enum PlaceStep {
+    Field(String),
+    /// The index and the index expression, whose span a bounds error points at.
+    Index(i32, ID),
+}
+
+#[derive(Debug, Clone)]
+pub enum ControlFlow {
+    Return(Value),
+    Break,
+    Continue,
+}
+
+/// Runtime storage for variables, keyed by the variable ids assigned by the `TypeAnalyzer`.
+/// The first frame holds top-level variables, every function call pushes a new one.
+#[derive(Debug, Clone)]
+pub struct Environment {
+    frames: Vec<IndexMap<ID, Value>>,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self {
+            frames: vec![IndexMap::new()],
+        }
+    }
+
+    pub fn push_frame(&mut self) {
+        self.frames.push(IndexMap::new());
+    }
+
+    pub fn pop_frame(&mut self) {
+        if self.frames.len() > 1 {
+            self.frames.pop();
+        }
+    }
+
+    pub fn define(&mut self, id: ID, value: Value) {
+        self.frames
+            .last_mut()
+            .expect("Environment has no frames")
+            .insert(id, value);
+    }
+
+    pub fn get(&self, id: ID) -> Option<&Value> {
+        self.frames
+            .last()
+            .and_then(|frame| frame.get(&id))
+            .or_else(|| self.frames.first().and_then(|frame| frame.get(&id)))
+    }
+
+    pub fn assign(&mut self, id: ID, value: Value) -> bool {
+        let last = self.frames.len() - 1;
+        for index in [last, 0] {
+            if let Some(slot) = self.frames[index].get_mut(&id) {
+                *slot = value;
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+pub struct Interpreter<'a> {
+    pub ctx: &'a GlobalContext,
+    pub content: String,
+    pub env: Environment,
+    pub value: Value,
+    pub control: Option<ControlFlow>,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(ctx: &'a GlobalContext, content: String, env: Environment) -> Self {
+        Self {
+            ctx,
+            content,
+            env,
+            value: Value::Void,
+            control: None,
+        }
+    }
+
+    /// Executes top-level statements in order and then calls `main`, if the program defines one.
+    /// Returns the exit status, the value `main` returns or 0.
+    pub fn run(&mut self, ast: &mut Ast, args: Vec<String>) -> Result<i32> {
+        for (id, item) in ast.items.clone() {
+            if let ItemKind::Stmt(_) = item.kind {
+                self.visit_item(ast, id)?;
+            }
+        }
+
+        let Some(main) = self.ctx.lookup_function("main") else {
+            return Ok(0);
+        };
+        let arguments = match self.ctx.functions[&main].parameters.len() {
+            0 => vec![],
+            _ => vec![Value::Array(args.into_iter().map(Value::String).collect())],
+        };
+
+        match self.call_function(ast, main, arguments)? {
+            Value::Int(status) => Ok(status),
+            _ => Ok(0),
+        }
+    }
+
+    /// Value of `expr`, or `None` when a `break`, `continue` or `return` inside it left the
+    /// expression. The caller then stops too, so the signal reaches its loop or function.
+    pub fn evaluate(&mut self, ast: &mut Ast, expr: ID) -> Result<Option<Value>> {
+        self.visit_expression(ast, expr)?;
+
+        if self.control.is_some() {
+            return Ok(None);
+        }
+        Ok(Some(self.value.clone()))
+    }
+
+    /// Executes statements in order and leaves the value of a trailing expression statement in
+    /// `self.value`. Stops early when a statement sets a control flow signal.
+    pub fn execute_body(&mut self, ast: &mut Ast, stmts: &[ID]) -> Result<()> {
+        self.value = Value::Void;
+
+        for stmt in stmts {
+            self.visit_statement(ast, *stmt)?;
+
+            if self.control.is_some() {
+                return Ok(());
+            }
+        }
+
+        let trailing_expr = stmts
+            .last()
+            .is_some_and(|stmt| matches!(ast.query_stmt(*stmt).kind, StmtKind::Expr(_)));
+        if !trailing_expr {
+            self.value = Value::Void;
+        }
+
+        Ok(())
+    }
+
+    /// Runs one iteration of a loop body. Returns `false` when the loop has to stop, either
+    /// because of `break` or because a `return` is unwinding through it.
+    pub fn execute_loop_body(&mut self, ast: &mut Ast, body: &[ID]) -> Result<bool> {
+        self.execute_body(ast, body)?;
+
+        match self.control {
+            Some(ControlFlow::Break) => {
+                self.control = None;
+                Ok(false)
+            }
+            Some(ControlFlow::Continue) => {
+                self.control = None;
+                Ok(true)
+            }
+            Some(ControlFlow::Return(_)) => Ok(false),
+            None => Ok(true),
+        }
+    }
+
+    /// Stores `value` in the place named by `place`, a variable or a chain of field accesses
+    /// and indices on one.
+    pub fn assign_place(&mut self, ast: &mut Ast, place: ID, value: Value) -> Result<()> {
+        match self.resolve_place(ast, place)? {
+            Some(place) => self.write_place(ast, &place, value),
+            None => Ok(()),
+        }
+    }
+
+    /// Evaluates the indices inside `place` once, from the outermost array inwards, like the
+    /// generated C++. Returns `None` when a `break`, `continue` or `return` left one of them.
+    pub fn resolve_place(&mut self, ast: &mut Ast, place: ID) -> Result<Option<Place>> {
+        let resolved = match ast.query_expr(place).kind.clone() {
+            ExprKind::Variable(variable) => Place {
+                variable: variable.variable_idx,
+                steps: vec![],
+            },
+            ExprKind::Parenthesized(parenthesized) => {
+                return self.resolve_place(ast, parenthesized.inner);
+            }
+            ExprKind::FieldAccess(access) => {
+                let Some(mut resolved) = self.resolve_place(ast, access.object)? else {
+                    return Ok(None);
+                };
+                resolved
+                    .steps
+                    .push(PlaceStep::Field(access.field_name().to_string()));
+                resolved
+            }
+            ExprKind::Index(index_expression) => {
+                let Some(mut resolved) = self.resolve_place(ast, index_expression.object)? else {
+                    return Ok(None);
+                };
+                let Some(index) = self.evaluate(ast, index_expression.index)? else {
+                    return Ok(None);
+                };
+                resolved.steps.push(PlaceStep::Index(index.as_int(), place));
+                resolved
+            }
+            _ => unreachable!("Assignment target was not checked by the type analyzer"),
+        };
+
+        Ok(Some(resolved))
+    }
+
+    /// Value stored in a place resolved by `resolve_place`.
+    pub fn read_place(&self, ast: &Ast, place: &Place) -> Result<Value> {
+        let mut value = self
+            .env
+            .get(place.variable)
+            .expect("Variable was not defined before its use");
+        for step in &place.steps {
+            value = match step {
+                PlaceStep::Field(name) => value.field(name),
+                PlaceStep::Index(index, expr) => {
+                    let elements = value.as_array();
+                    &elements[self.check_index(ast, *expr, elements.len(), *index)?]
+                }
+            };
+        }
+
+        Ok(value.clone())
+    }
+
+    /// Stores `value` in a place resolved by `resolve_place`. Structs and arrays are values, so
+    /// the variable is written back as a whole.
+    pub fn write_place(&mut self, ast: &Ast, place: &Place, value: Value) -> Result<()> {
+        let mut root = self
+            .env
+            .get(place.variable)
+            .cloned()
+            .expect("Variable was not defined before its use");
+        let mut slot = &mut root;
+        for step in &place.steps {
+            slot = match step {
+                PlaceStep::Field(name) => slot.field_mut(name),
+                PlaceStep::Index(index, expr) => {
+                    let elements = slot.as_array_mut();
+                    let index = self.check_index(ast, *expr, elements.len(), *index)?;
+                    &mut elements[index]
+                }
+            };
+        }
+        *slot = value;
+        self.env.assign(place.variable, root);
+
+        Ok(())
+    }
+
+    /// Converts `index` into a position inside an array of `length` elements, the generated
+    /// C++ aborts with the same message in debug builds.
+    pub fn check_index(&self, ast: &Ast, expr: ID, length: usize, index: i32) -> Result<usize> {
+        if index < 0 || index as usize >= length {
+            return Err(RuntimeError(
+                format!(
+                    "Index out of bounds: the length is {} but the index is {}",
+                    length, index
+                ),
+                ast.query_expr(expr).span(ast),
+                self.content.clone(),
+            ));
+        }
+
+        Ok(index as usize)
+    }
+
+    pub fn call_function(
+        &mut self,
+        ast: &mut Ast,
+        function: ID,
+        arguments: Vec<Value>,
+    ) -> Result<Value> {
+        let function = self
+            .ctx
+            .functions
+            .get(&function)
+            .expect("Function was not registered in the global context");
+
+        self.env.push_frame();
+        for (param, argument) in function.parameters.iter().zip(arguments) {
+            self.env.define(*param, argument);
+        }
+
+        let result = self.execute_body(ast, &function.body.stmts);
+        self.env.pop_frame();
+        result?;
+
+        // Without a `return`, the value of the trailing expression is returned.
+        let value = match self.control.take() {
+            Some(ControlFlow::Return(value)) => value,
+            _ if !matches!(function.return_type, Type::Void) => self.value.clone(),
+            _ => Value::Void,
+        };
+        self.value = value.clone();
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::interpreter::Value;
+use std::io::Write;
+
+/// Runs a function from the bundled std, mirroring the C++ implementations in `cli/lib`.
+/// `std::array` functions modify their first argument in place, the caller stores it back.
+pub fn call(module: &str, function: &str, arguments: &mut [Value]) -> Result<Value, String> {
+    match (module, function) {
+        ("io", "print") => {
+            print!("{}", join(arguments));
+            std::io::stdout().flush().map_err(|e| e.to_string())?;
+            Ok(Value::Void)
+        }
+        ("io", "println") => {
+            println!("{}", join(arguments));
+            Ok(Value::Void)
+        }
+        ("io", "eprint") => {
+            eprint!("{}", join(arguments));
+            Ok(Value::Void)
+        }
+        ("io", "eprintln") => {
+            eprintln!("{}", join(arguments));
+            Ok(Value::Void)
+        }
+        ("math", "sqrt") => Ok(Value::Float(arguments[0].as_float().sqrt())),
+        ("env", "get_cwd") => std::env::current_dir()
+            .map(|dir| Value::String(dir.display().to_string()))
+            .map_err(|_| "NotFound: Could not get current working directory.".to_string()),
+        ("env", "get_home") => std::env::var("HOME")
+            .map(Value::String)
+            .map_err(|_| "NotFound: Could not get home directory.".to_string()),
+        ("array", "len") => Ok(Value::Int(arguments[0].as_array().len() as i32)),
+        ("array", "push") => {
+            let value = arguments[1].clone();
+            arguments[0].as_array_mut().push(value);
+            Ok(Value::Void)
+        }
+        ("array", "pop") => arguments[0]
+            .as_array_mut()
+            .pop()
+            .ok_or_else(|| "Cannot pop from an empty array".to_string()),
+        _ => Err(format!("std::{}::{} is not available", module, function)),
+    }
+}
+
+/// Integer exponentiation used for `**`, like `math::ipow` in `cli/lib/math.cpp`. Overflow
+/// wraps around, and a negative exponent truncates toward zero like integer division.
+pub fn power(base: i32, exponent: i32) -> i32 {
+    if exponent < 0 {
+        return match base {
+            1 => 1,
+            -1 if exponent % 2 == 0 => 1,
+            -1 => -1,
+            _ => 0,
+        };
+    }
+
+    let (mut base, mut exponent, mut result) = (base, exponent, 1i32);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+fn join(arguments: &[Value]) -> String {
+    arguments
+        .iter()
+        .map(|argument| argument.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
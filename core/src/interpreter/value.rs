@@ -0,0 +1,151 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// 32 bits with wrapping arithmetic, like the `int` of the generated C++.
+    Int(i32),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    /// Struct name and field values in declaration order.
+    Struct(String, Vec<(String, Value)>),
+    /// Enum name, variant name and payload.
+    Enum(String, String, Vec<Value>),
+    Array(Vec<Value>),
+    Void,
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", format_float(*value)),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::String(value) => write!(f, "{}", value),
+            Value::Struct(name, fields) => {
+                write!(f, "{} {{ ", name)?;
+                for (index, (field, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", field, value)?;
+                }
+                write!(f, " }}")
+            }
+            Value::Enum(_, variant, payload) => {
+                write!(f, "{}", variant)?;
+                if !payload.is_empty() {
+                    let payload: Vec<String> = payload.iter().map(|v| v.to_string()).collect();
+                    write!(f, "({})", payload.join(", "))?;
+                }
+                Ok(())
+            }
+            Value::Array(elements) => {
+                let elements: Vec<String> = elements.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Value::Void => write!(f, "()"),
+        }
+    }
+}
+
+impl Value {
+    pub fn as_int(&self) -> i32 {
+        match self {
+            Value::Int(value) => *value,
+            _ => unreachable!("Expected int value, got {:?}", self),
+        }
+    }
+
+    pub fn as_float(&self) -> f64 {
+        match self {
+            Value::Float(value) => *value,
+            _ => unreachable!("Expected float value, got {:?}", self),
+        }
+    }
+
+    pub fn as_bool(&self) -> bool {
+        match self {
+            Value::Bool(value) => *value,
+            _ => unreachable!("Expected bool value, got {:?}", self),
+        }
+    }
+
+    pub fn as_array(&self) -> &Vec<Value> {
+        match self {
+            Value::Array(elements) => elements,
+            _ => unreachable!("Expected array value, got {:?}", self),
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> &mut Vec<Value> {
+        match self {
+            Value::Array(elements) => elements,
+            _ => unreachable!("Expected array value, got {:?}", self),
+        }
+    }
+
+    pub fn field(&self, name: &str) -> &Value {
+        match self {
+            Value::Struct(_, fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value)
+                .expect("Field was not resolved by the type analyzer"),
+            _ => unreachable!("Expected struct value, got {:?}", self),
+        }
+    }
+
+    pub fn field_mut(&mut self, name: &str) -> &mut Value {
+        match self {
+            Value::Struct(_, fields) => fields
+                .iter_mut()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value)
+                .expect("Field was not resolved by the type analyzer"),
+            _ => unreachable!("Expected struct value, got {:?}", self),
+        }
+    }
+
+    /// Representation used when echoing values back to the user, strings are quoted.
+    pub fn repr(&self) -> String {
+        match self {
+            Value::String(value) => format!("{:?}", value),
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// Formats like `std::cout << value` does by default (`%g`), so the interpreter prints floats
+/// the same way as the compiled program: six significant digits without trailing zeros.
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        return "nan".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "inf" } else { "-inf" }.to_string();
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    let scientific = format!("{:.5e}", value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+
+    if exponent < -4 || exponent >= 6 {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", trim_fraction(mantissa), sign, exponent.abs())
+    } else {
+        let fixed = format!("{:.*}", (5 - exponent) as usize, value);
+        trim_fraction(&fixed).to_string()
+    }
+}
+
+fn trim_fraction(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
@@ -0,0 +1,616 @@
+use crate::ast::expr::{
+    ArrayLiteralExpr, AssignExpr, BinOpKind, BinaryExpr, BlockExpr, BoolExpr, CallExpr, CastExpr,
+    EnumVariantExpr, Expr, ExprKind, FieldAccessExpr, FloatExpr, IfExpr, IndexExpr, MatchExpr,
+    NumberExpr, Pattern, PlaceAssignExpr, StringExpr, StructLiteralExpr, UnOpKind, UnaryExpr,
+    VarExpr,
+};
+use crate::ast::function::{Body, FunctionDeclaration};
+use crate::ast::span::TextSpan;
+use crate::ast::stmt::{BreakStmt, ContinueStmt, ForStmt, LetStmt, ReturnStmt, Stmt, WhileStmt};
+use crate::ast::visitor::ASTWalker;
+use crate::ast::{Ast, ID};
+use crate::error::error::Error::RuntimeError;
+use crate::interpreter::{stdlib, ControlFlow, Interpreter, Value};
+use crate::semantic::types::STD_RESERVED_WORDS;
+use crate::types::Type;
+use crate::Result;
+
+impl ASTWalker for Interpreter<'_> {
+    // Functions are looked up in the global context when they are called.
+    fn visit_func_decl(
+        &mut self,
+        _ast: &mut Ast,
+        _func_decl: &FunctionDeclaration,
+        _item_id: ID,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_body(&mut self, ast: &mut Ast, body: &Body) -> Result<()> {
+        self.execute_body(ast, &body.stmts)
+    }
+
+    fn visit_return_statement(
+        &mut self,
+        ast: &mut Ast,
+        return_statement: &ReturnStmt,
+    ) -> Result<()> {
+        let value = match return_statement.return_value {
+            Some(expr) => match self.evaluate(ast, expr)? {
+                Some(value) => value,
+                None => return Ok(()),
+            },
+            None => Value::Void,
+        };
+        self.control = Some(ControlFlow::Return(value));
+
+        Ok(())
+    }
+
+    fn visit_while_statement(&mut self, ast: &mut Ast, while_statement: &WhileStmt) -> Result<()> {
+        // Only a `return` can leave the condition, the type analyzer rejects `break` and
+        // `continue` there.
+        while let Some(condition) = self.evaluate(ast, while_statement.condition)? {
+            if !condition.as_bool() || !self.execute_loop_body(ast, &while_statement.body.stmts)? {
+                break;
+            }
+        }
+        self.value = Value::Void;
+
+        Ok(())
+    }
+
+    fn visit_for_statement(
+        &mut self,
+        ast: &mut Ast,
+        for_statement: &ForStmt,
+        _stmt: &Stmt,
+    ) -> Result<()> {
+        let Some(start) = self.evaluate(ast, for_statement.start)? else {
+            return Ok(());
+        };
+        let Some(end) = self.evaluate(ast, for_statement.end)? else {
+            return Ok(());
+        };
+        let (start, end) = (start.as_int(), end.as_int());
+        let step = match for_statement.step {
+            Some(step) => {
+                let Some(value) = self.evaluate(ast, step)? else {
+                    return Ok(());
+                };
+                let value = value.as_int();
+                if value <= 0 {
+                    return Err(RuntimeError(
+                        format!("Step of a for loop must be positive, got {}", value),
+                        ast.query_expr(step).span(ast),
+                        self.content.clone(),
+                    ));
+                }
+                value
+            }
+            None => 1,
+        };
+
+        // Like the generated C++ loop, the counter is read back from the variable after every
+        // iteration and the bounds are evaluated once.
+        let var = for_statement.variable_id;
+        self.env.define(var, Value::Int(start));
+        loop {
+            let current = self.env.get(var).map(Value::as_int).unwrap_or(start);
+            let in_range = if for_statement.inclusive {
+                current <= end
+            } else {
+                current < end
+            };
+            if !in_range || !self.execute_loop_body(ast, &for_statement.body.stmts)? {
+                break;
+            }
+
+            let current = self.env.get(var).map(Value::as_int).unwrap_or(start);
+            match current.checked_add(step) {
+                Some(next) => self.env.assign(var, Value::Int(next)),
+                None => break,
+            };
+        }
+        self.value = Value::Void;
+
+        Ok(())
+    }
+
+    fn visit_break_statement(
+        &mut self,
+        _ast: &mut Ast,
+        _break_statement: &BreakStmt,
+    ) -> Result<()> {
+        self.control = Some(ControlFlow::Break);
+
+        Ok(())
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        _ast: &mut Ast,
+        _continue_statement: &ContinueStmt,
+    ) -> Result<()> {
+        self.control = Some(ControlFlow::Continue);
+
+        Ok(())
+    }
+
+    fn visit_block_expr(
+        &mut self,
+        ast: &mut Ast,
+        block_expr: &BlockExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.execute_body(ast, &block_expr.stmts)
+    }
+
+    fn visit_if_expression(&mut self, ast: &mut Ast, if_expr: &IfExpr, _expr: &Expr) -> Result<()> {
+        let Some(condition) = self.evaluate(ast, if_expr.condition)? else {
+            return Ok(());
+        };
+        if condition.as_bool() {
+            self.visit_body(ast, &if_expr.then_branch)?;
+        } else if let Some(else_branch) = &if_expr.else_branch {
+            self.visit_body(ast, &else_branch.body)?;
+        } else {
+            self.value = Value::Void;
+        }
+
+        Ok(())
+    }
+
+    fn visit_let_statement(
+        &mut self,
+        ast: &mut Ast,
+        let_statement: &LetStmt,
+        _stmt: &Stmt,
+    ) -> Result<()> {
+        let Some(value) = self.evaluate(ast, let_statement.initializer)? else {
+            return Ok(());
+        };
+        self.env.define(let_statement.variable_id, value);
+        self.value = Value::Void;
+
+        Ok(())
+    }
+
+    fn visit_call_expression(
+        &mut self,
+        ast: &mut Ast,
+        call_expression: &CallExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let name = call_expression.function_name();
+        let module = match call_expression.scope {
+            Some(scope) => match &ast.query_expr(scope).kind {
+                ExprKind::ScopedIdentifier { path } => {
+                    path.last().map(|token| token.span.literal.clone())
+                }
+                _ => unreachable!("Invalid scope"),
+            },
+            None if STD_RESERVED_WORDS.contains(&name) => Some("io".to_string()),
+            None => None,
+        };
+
+        // `std::array` functions other than `len` change the array in place, its indices are
+        // evaluated once and the array is written back through them.
+        let mut place = None;
+        let mut arguments = vec![];
+        if module.as_deref() == Some("array") && name != "len" {
+            let Some(resolved) = self.resolve_place(ast, call_expression.arguments[0])? else {
+                return Ok(());
+            };
+            arguments.push(self.read_place(ast, &resolved)?);
+            place = Some(resolved);
+        }
+        for argument in &call_expression.arguments[arguments.len()..] {
+            match self.evaluate(ast, *argument)? {
+                Some(argument) => arguments.push(argument),
+                None => return Ok(()),
+            }
+        }
+
+        self.value = match module {
+            Some(module) => {
+                let value = stdlib::call(&module, name, &mut arguments).map_err(|message| {
+                    RuntimeError(
+                        message,
+                        call_expression.callee.span.clone(),
+                        self.content.clone(),
+                    )
+                })?;
+                if let Some(place) = place {
+                    let array = arguments.swap_remove(0);
+                    self.write_place(ast, &place, array)?;
+                }
+                value
+            }
+            None => {
+                let function = self
+                    .ctx
+                    .lookup_function(name)
+                    .expect("Call was not resolved by the type analyzer");
+
+                self.call_function(ast, function, arguments)?
+            }
+        };
+
+        Ok(())
+    }
+
+    fn visit_assignment_expression(
+        &mut self,
+        ast: &mut Ast,
+        assignment_expression: &AssignExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let Some(value) = self.evaluate(ast, assignment_expression.expression)? else {
+            return Ok(());
+        };
+        self.env
+            .assign(assignment_expression.variable_idx, value.clone());
+        self.value = value;
+
+        Ok(())
+    }
+
+    fn visit_struct_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        struct_literal: &StructLiteralExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let structure = self
+            .ctx
+            .get_struct(struct_literal.struct_name())
+            .expect("Struct was not resolved by the type analyzer");
+
+        // Fields are evaluated in declaration order, like the aggregate initializer in C++.
+        let mut fields = vec![];
+        for field in &structure.fields {
+            let initializer = struct_literal
+                .fields
+                .iter()
+                .find(|initializer| initializer.identifier.span.literal == field.name)
+                .expect("Missing field was not reported by the type analyzer");
+            match self.evaluate(ast, initializer.value)? {
+                Some(value) => fields.push((field.name.clone(), value)),
+                None => return Ok(()),
+            }
+        }
+        self.value = Value::Struct(structure.name.clone(), fields);
+
+        Ok(())
+    }
+
+    fn visit_field_access_expression(
+        &mut self,
+        ast: &mut Ast,
+        field_access: &FieldAccessExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let Some(object) = self.evaluate(ast, field_access.object)? else {
+            return Ok(());
+        };
+        self.value = object.field(field_access.field_name()).clone();
+
+        Ok(())
+    }
+
+    fn visit_array_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        array_literal: &ArrayLiteralExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let mut elements = vec![];
+        for element in &array_literal.elements {
+            match self.evaluate(ast, *element)? {
+                Some(element) => elements.push(element),
+                None => return Ok(()),
+            }
+        }
+        self.value = Value::Array(elements);
+
+        Ok(())
+    }
+
+    fn visit_index_expression(
+        &mut self,
+        ast: &mut Ast,
+        index_expression: &IndexExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        let Some(object) = self.evaluate(ast, index_expression.object)? else {
+            return Ok(());
+        };
+        let Some(index) = self.evaluate(ast, index_expression.index)? else {
+            return Ok(());
+        };
+        let elements = object.as_array();
+        let index = self.check_index(ast, expr.id, elements.len(), index.as_int())?;
+        self.value = elements[index].clone();
+
+        Ok(())
+    }
+
+    fn visit_place_assignment_expression(
+        &mut self,
+        ast: &mut Ast,
+        place_assignment: &PlaceAssignExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let Some(value) = self.evaluate(ast, place_assignment.expression)? else {
+            return Ok(());
+        };
+        self.assign_place(ast, place_assignment.target, value.clone())?;
+        self.value = value;
+
+        Ok(())
+    }
+
+    fn visit_enum_variant_expression(
+        &mut self,
+        ast: &mut Ast,
+        enum_variant: &EnumVariantExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let mut payload = vec![];
+        for argument in &enum_variant.arguments {
+            match self.evaluate(ast, *argument)? {
+                Some(argument) => payload.push(argument),
+                None => return Ok(()),
+            }
+        }
+        self.value = Value::Enum(
+            enum_variant.enum_name().to_string(),
+            enum_variant.variant_name().to_string(),
+            payload,
+        );
+
+        Ok(())
+    }
+
+    fn visit_match_expression(
+        &mut self,
+        ast: &mut Ast,
+        match_expr: &MatchExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let Some(scrutinee) = self.evaluate(ast, match_expr.scrutinee)? else {
+            return Ok(());
+        };
+
+        for arm in &match_expr.arms {
+            let matched = match &arm.pattern {
+                Pattern::Wildcard(_) => true,
+                Pattern::Literal(literal) => {
+                    self.evaluate(ast, *literal)?.as_ref() == Some(&scrutinee)
+                }
+                Pattern::Variant(pattern) => match &scrutinee {
+                    Value::Enum(_, variant, payload) if variant == pattern.variant_name() => {
+                        for (binding, value) in pattern.bindings.iter().zip(payload) {
+                            if !binding.is_wildcard() {
+                                self.env.define(binding.variable_id, value.clone());
+                            }
+                        }
+                        true
+                    }
+                    _ => false,
+                },
+            };
+
+            if matched {
+                return self.visit_expression(ast, arm.body);
+            }
+        }
+
+        unreachable!("Match was not checked for exhaustiveness by the type analyzer")
+    }
+
+    fn visit_variable_expression(
+        &mut self,
+        _ast: &mut Ast,
+        variable_expression: &VarExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.value = match self.env.get(variable_expression.variable_idx) {
+            Some(value) => value.clone(),
+            None => {
+                return Err(RuntimeError(
+                    format!(
+                        "Variable {} was used before it was initialized",
+                        variable_expression.identifier()
+                    ),
+                    variable_expression.identifier.span.clone(),
+                    self.content.clone(),
+                ))
+            }
+        };
+
+        Ok(())
+    }
+
+    fn visit_number_expression(
+        &mut self,
+        _ast: &mut Ast,
+        number: &NumberExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.value = Value::Int(number.number as i32);
+
+        Ok(())
+    }
+
+    fn visit_string_expression(
+        &mut self,
+        _ast: &mut Ast,
+        string: &StringExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.value = Value::String(string.string.clone());
+
+        Ok(())
+    }
+
+    fn visit_boolean_expression(
+        &mut self,
+        _ast: &mut Ast,
+        boolean: &BoolExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.value = Value::Bool(boolean.value);
+
+        Ok(())
+    }
+
+    fn visit_error(&mut self, _ast: &mut Ast, span: &TextSpan) -> Result<()> {
+        Err(RuntimeError(
+            "Tried to evaluate an invalid expression".to_string(),
+            span.clone(),
+            self.content.clone(),
+        ))
+    }
+
+    fn visit_float_expression(
+        &mut self,
+        _ast: &mut Ast,
+        number: &FloatExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.value = Value::Float(number.number);
+
+        Ok(())
+    }
+
+    fn visit_cast_expression(&mut self, ast: &mut Ast, cast: &CastExpr, expr: &Expr) -> Result<()> {
+        let Some(value) = self.evaluate(ast, cast.expression)? else {
+            return Ok(());
+        };
+        // Floats are truncated toward zero, like `static_cast<int>` in the generated C++.
+        self.value = match (value, &expr.ty) {
+            (Value::Int(value), Type::Float) => Value::Float(value as f64),
+            (Value::Float(value), Type::Int) => Value::Int(value as i32),
+            (value, _) => value,
+        };
+
+        Ok(())
+    }
+
+    fn visit_unary_expression(
+        &mut self,
+        ast: &mut Ast,
+        unary_expression: &UnaryExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let Some(operand) = self.evaluate(ast, unary_expression.operand)? else {
+            return Ok(());
+        };
+        if let UnOpKind::LogicalNot = unary_expression.operator.kind {
+            self.value = Value::Bool(!operand.as_bool());
+            return Ok(());
+        }
+
+        if let Value::Float(operand) = operand {
+            self.value = Value::Float(-operand);
+            return Ok(());
+        }
+
+        let operand = operand.as_int();
+        self.value = match unary_expression.operator.kind {
+            UnOpKind::Minus => Value::Int(operand.wrapping_neg()),
+            UnOpKind::BitwiseNot => Value::Int(!operand),
+            UnOpKind::LogicalNot => unreachable!(),
+        };
+
+        Ok(())
+    }
+
+    fn visit_binary_expression(
+        &mut self,
+        ast: &mut Ast,
+        binary_expression: &BinaryExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        let operator = &binary_expression.operator;
+        let Some(left) = self.evaluate(ast, binary_expression.left)? else {
+            return Ok(());
+        };
+
+        // The right operand of `&&` and `||` is only evaluated when it decides the result.
+        match operator.kind {
+            BinOpKind::LogicalAnd if !left.as_bool() => {
+                self.value = left;
+                return Ok(());
+            }
+            BinOpKind::LogicalOr if left.as_bool() => {
+                self.value = left;
+                return Ok(());
+            }
+            BinOpKind::LogicalAnd | BinOpKind::LogicalOr => {
+                return self.visit_expression(ast, binary_expression.right);
+            }
+            _ => {}
+        }
+
+        let Some(right) = self.evaluate(ast, binary_expression.right)? else {
+            return Ok(());
+        };
+
+        self.value = match operator.kind {
+            BinOpKind::Equals => Value::Bool(left == right),
+            BinOpKind::NotEquals => Value::Bool(left != right),
+            // Division by zero follows IEEE 754 and produces an infinity or NaN.
+            kind if matches!(left, Value::Float(_)) => {
+                let (left, right) = (left.as_float(), right.as_float());
+
+                match kind {
+                    BinOpKind::Plus => Value::Float(left + right),
+                    BinOpKind::Minus => Value::Float(left - right),
+                    BinOpKind::Multiply => Value::Float(left * right),
+                    BinOpKind::Divide => Value::Float(left / right),
+                    BinOpKind::LessThan => Value::Bool(left < right),
+                    BinOpKind::LessThanOrEqual => Value::Bool(left <= right),
+                    BinOpKind::GreaterThan => Value::Bool(left > right),
+                    BinOpKind::GreaterThanOrEqual => Value::Bool(left >= right),
+                    _ => unreachable!("{:?} is not defined for floats", kind),
+                }
+            }
+            kind => {
+                let (left, right) = (left.as_int(), right.as_int());
+
+                if matches!(kind, BinOpKind::Divide | BinOpKind::Modulo) && right == 0 {
+                    return Err(RuntimeError(
+                        "Attempted to divide by zero".to_string(),
+                        operator.token.span.clone(),
+                        self.content.clone(),
+                    ));
+                }
+
+                match kind {
+                    BinOpKind::Plus => Value::Int(left.wrapping_add(right)),
+                    BinOpKind::Minus => Value::Int(left.wrapping_sub(right)),
+                    BinOpKind::Multiply => Value::Int(left.wrapping_mul(right)),
+                    BinOpKind::Divide => Value::Int(left.wrapping_div(right)),
+                    BinOpKind::Modulo => Value::Int(left.wrapping_rem(right)),
+                    BinOpKind::Power => Value::Int(stdlib::power(left, right)),
+                    BinOpKind::BitwiseAnd => Value::Int(left & right),
+                    BinOpKind::BitwiseOr => Value::Int(left | right),
+                    BinOpKind::BitwiseXor => Value::Int(left ^ right),
+                    BinOpKind::LessThan => Value::Bool(left < right),
+                    BinOpKind::LessThanOrEqual => Value::Bool(left <= right),
+                    BinOpKind::GreaterThan => Value::Bool(left > right),
+                    BinOpKind::GreaterThanOrEqual => Value::Bool(left >= right),
+                    BinOpKind::Equals
+                    | BinOpKind::NotEquals
+                    | BinOpKind::LogicalAnd
+                    | BinOpKind::LogicalOr => unreachable!(),
+                }
+            }
+        };
+
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
 use crate::ast::position::Position;
 use crate::ast::span::TextSpan;
+use crate::error::error::Error;
+use crate::error::error::Error::{
+    IntegerOutOfRange, InvalidEscape, UnterminatedComment, UnterminatedString,
+};
 use crate::lexer::token::{Keyword, Operator, Separator, Token, TokenKind};
+use crate::Result;
 
 pub mod token;
 
@@ -8,6 +13,8 @@ pub struct Lexer<'a> {
     input: &'a str,
     pub pos: Position,
     pub current_pos: usize,
+    /// Set when a string or number literal is malformed, reported by `tokenize`.
+    error: Option<Error>,
 }
 
 impl<'a> Lexer<'a> {
@@ -16,11 +23,46 @@ impl<'a> Lexer<'a> {
             input,
             current_pos: 0,
             pos: Position::new(0, 0, 0),
+            error: None,
         }
     }
 
+    /// Collects the remaining tokens up to and including `TokenKind::Eof`, skipping whitespace
+    /// and comments.
+    pub fn tokenize(&mut self) -> Result<Vec<Token>> {
+        let mut tokens: Vec<Token> = vec![];
+
+        while let Some(token) = self.next_token() {
+            if let Some(error) = self.error.take() {
+                return Err(error);
+            }
+            match token.kind {
+                TokenKind::Whitespace
+                | TokenKind::LineComment
+                | TokenKind::BlockComment { terminated: true } => continue,
+                TokenKind::BlockComment { terminated: false } => {
+                    let start = token.span.start;
+                    let end = Position::new(start.line, start.column + 2, start.index + 2);
+
+                    return Err(UnterminatedComment(
+                        TextSpan::new(start, end, "/*".to_string()),
+                        self.input.to_string(),
+                    ));
+                }
+                _ => {}
+            }
+            if token.kind == TokenKind::Eof {
+                tokens.push(token);
+                break;
+            }
+            tokens.push(token);
+        }
+
+        Ok(tokens)
+    }
+
     pub fn next_token(&mut self) -> Option<Token> {
-        if self.current_pos >= self.input.len() {
+        if self.current_pos >= self.input.chars().count() {
             self.current_pos += 1;
             return Some(Token::new(
                 TokenKind::Eof,
@@ -32,10 +74,29 @@ impl<'a> Lexer<'a> {
         let start_pos = self.pos;
 
         let kind = if Self::is_number_start(&c) {
-            TokenKind::Number(self.consume_number())
+            match self.consume_number(start_pos) {
+                Ok(kind) => kind,
+                Err(error) => {
+                    self.error = Some(error);
+                    TokenKind::Bad
+                }
+            }
         } else if Self::is_string_start(&c) {
             self.consume();
-            TokenKind::String(self.consume_string())
+            match self.consume_string(start_pos) {
+                Ok(string) => TokenKind::String(string),
+                Err(error) => {
+                    self.error = Some(error);
+                    TokenKind::Bad
+                }
+            }
+        } else if c == '/' && self.peek_char(1) == Some('/') {
+            self.consume_line_comment();
+            TokenKind::LineComment
+        } else if c == '/' && self.peek_char(1) == Some('*') {
+            TokenKind::BlockComment {
+                terminated: self.consume_block_comment(),
+            }
         } else if Self::is_whitespace(&c) {
             self.consume();
             TokenKind::Whitespace
@@ -49,6 +110,14 @@ impl<'a> Lexer<'a> {
                 "while" => TokenKind::Keyword(Keyword::While),
                 "fn" => TokenKind::Keyword(Keyword::Fn),
                 "return" => TokenKind::Keyword(Keyword::Return),
+                "for" => TokenKind::Keyword(Keyword::For),
+                "in" => TokenKind::Keyword(Keyword::In),
+                "break" => TokenKind::Keyword(Keyword::Break),
+                "continue" => TokenKind::Keyword(Keyword::Continue),
+                "struct" => TokenKind::Keyword(Keyword::Struct),
+                "enum" => TokenKind::Keyword(Keyword::Enum),
+                "match" => TokenKind::Keyword(Keyword::Match),
+                "as" => TokenKind::Keyword(Keyword::As),
                 _ => TokenKind::Identifier,
             }
         } else {
@@ -56,7 +125,7 @@ impl<'a> Lexer<'a> {
         };
 
         let end_pos = self.pos;
-        let literal = self.input[start_pos.index..end_pos.index].to_string();
+        let literal = self.slice(start_pos.index, end_pos.index);
 
         Some(Token::new(kind, TextSpan::new(start_pos, end_pos, literal)))
     }
@@ -79,13 +148,25 @@ impl<'a> Lexer<'a> {
             '/' => TokenKind::Operator(Operator::Slash),
             '(' => TokenKind::Separator(Separator::LeftParen),
             ')' => TokenKind::Separator(Separator::RightParen),
+            '=' if self.current_char() == Some('>') => {
+                self.consume();
+                TokenKind::Separator(Separator::FatArrow)
+            }
             '=' => self.lex_potential_double_char_operator(
                 '=',
                 TokenKind::Operator(Operator::Equals),
                 TokenKind::Operator(Operator::EqualsEquals),
             ),
-            '&' => TokenKind::Operator(Operator::Ampersand),
-            '|' => TokenKind::Operator(Operator::Pipe),
+            '&' => self.lex_potential_double_char_operator(
+                '&',
+                TokenKind::Operator(Operator::Ampersand),
+                TokenKind::Operator(Operator::AmpersandAmpersand),
+            ),
+            '|' => self.lex_potential_double_char_operator(
+                '|',
+                TokenKind::Operator(Operator::Pipe),
+                TokenKind::Operator(Operator::PipePipe),
+            ),
             '^' => TokenKind::Operator(Operator::Caret),
             '~' => TokenKind::Operator(Operator::Tilde),
             '>' => self.lex_potential_double_char_operator(
@@ -100,9 +181,11 @@ impl<'a> Lexer<'a> {
             ),
             '!' => self.lex_potential_double_char_operator(
                 '=',
-                TokenKind::Bad,
+                TokenKind::Operator(Operator::Bang),
                 TokenKind::Operator(Operator::BangEquals),
             ),
+            '[' => TokenKind::Separator(Separator::LeftBracket),
+            ']' => TokenKind::Separator(Separator::RightBracket),
             '{' => TokenKind::Separator(Separator::OpenBrace),
             '}' => TokenKind::Separator(Separator::CloseBrace),
             ',' => TokenKind::Separator(Separator::Comma),
@@ -112,6 +195,15 @@ impl<'a> Lexer<'a> {
                 TokenKind::Separator(Separator::Scope),
             ),
             ';' => TokenKind::Separator(Separator::SemiColon),
+            '.' if self.current_char() == Some('.') => {
+                self.consume();
+                self.lex_potential_double_char_operator(
+                    '=',
+                    TokenKind::Separator(Separator::DotDot),
+                    TokenKind::Separator(Separator::DotDotEquals),
+                )
+            }
+            '.' => TokenKind::Separator(Separator::Dot),
             '"' => TokenKind::Separator(Separator::Quote),
             _ => TokenKind::Bad,
         }
@@ -143,18 +235,111 @@ impl<'a> Lexer<'a> {
         c == &'\"'
     }
 
-    fn consume_string(&mut self) -> String {
+    /// Consumes a string literal after its opening quote at `start`, decoding escapes.
+    fn consume_string(&mut self, start: Position) -> Result<String> {
         let mut string = String::new();
+        loop {
+            let escape_start = self.pos;
+            match self.consume() {
+                Some('"') => return Ok(string),
+                Some('\\') if self.current_char().is_some() => {
+                    string.push(self.consume_escape(escape_start)?)
+                }
+                Some(c) => string.push(c),
+                None => {
+                    let end = Position::new(start.line, start.column + 1, start.index + 1);
+                    return Err(UnterminatedString(
+                        TextSpan::new(start, end, "\"".to_string()),
+                        self.input.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Decodes the escape sequence after a `\` that started at `start`.
+    fn consume_escape(&mut self, start: Position) -> Result<char> {
+        let escaped = match self.consume() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('u') => return self.consume_unicode_escape(start),
+            _ => return Err(self.invalid_escape(start)),
+        };
+
+        Ok(escaped)
+    }
+
+    /// Decodes the `{...}` part of `\u{...}`, one to six hex digits naming a Unicode scalar.
+    fn consume_unicode_escape(&mut self, start: Position) -> Result<char> {
+        if self.current_char() != Some('{') {
+            return Err(self.invalid_escape(start));
+        }
+        self.consume();
+
+        let mut digits = String::new();
         while let Some(c) = self.current_char() {
-            if c != '\"' {
-                self.consume().unwrap();
-                string.push(c);
+            if !c.is_ascii_hexdigit() || digits.len() == 6 {
+                break;
+            }
+            self.consume();
+            digits.push(c);
+        }
+
+        if digits.is_empty() || self.current_char() != Some('}') {
+            return Err(self.invalid_escape(start));
+        }
+        self.consume();
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.invalid_escape(start))
+    }
+
+    fn invalid_escape(&self, start: Position) -> Error {
+        let literal = self.slice(start.index, self.pos.index);
+
+        InvalidEscape(
+            literal.clone(),
+            TextSpan::new(start, self.pos, literal),
+            self.input.to_string(),
+        )
+    }
+
+    fn consume_line_comment(&mut self) {
+        while let Some(c) = self.current_char() {
+            if c == '\n' {
+                break;
+            }
+            self.consume();
+        }
+    }
+
+    /// Consumes a possibly nested `/* */` comment, returns whether it was closed before EOF.
+    fn consume_block_comment(&mut self) -> bool {
+        let mut depth = 0;
+
+        while let Some(c) = self.current_char() {
+            if c == '/' && self.peek_char(1) == Some('*') {
+                self.consume();
+                self.consume();
+                depth += 1;
+            } else if c == '*' && self.peek_char(1) == Some('/') {
+                self.consume();
+                self.consume();
+                depth -= 1;
+
+                if depth == 0 {
+                    return true;
+                }
             } else {
                 self.consume();
-                break;
             }
         }
-        string
+
+        false
     }
 
     fn is_identifier_start(c: &char) -> bool {
@@ -169,16 +354,23 @@ impl<'a> Lexer<'a> {
         self.input.chars().nth(self.current_pos)
     }
 
+    fn peek_char(&self, offset: usize) -> Option<char> {
+        self.input.chars().nth(self.current_pos + offset)
+    }
+
     fn consume(&mut self) -> Option<char> {
-        if self.current_pos >= self.input.len() {
-            return None;
-        }
-        let c = self.current_char();
+        let c = self.current_char()?;
         self.current_pos += 1;
 
-        self.update_position(c?);
+        self.update_position(c);
 
-        c
+        Some(c)
+    }
+
+    /// The input between two character positions. Positions count characters, not bytes, so
+    /// they cannot index into `input` directly.
+    fn slice(&self, start: usize, end: usize) -> String {
+        self.input.chars().skip(start).take(end - start).collect()
     }
 
     fn consume_identifier(&mut self) -> String {
@@ -194,17 +386,52 @@ impl<'a> Lexer<'a> {
         identifier
     }
 
-    fn consume_number(&mut self) -> i64 {
-        let mut number: i64 = 0;
+    /// Consumes an integer, or a float when a fraction or an exponent follows. A `.` only
+    /// starts a fraction before a digit, so `0..10` stays a range.
+    fn consume_number(&mut self, start: Position) -> Result<TokenKind> {
+        let mut is_float = false;
+        self.consume_digits();
+
+        if self.current_char() == Some('.') && self.peek_char(1).is_some_and(|c| c.is_digit(10)) {
+            self.consume();
+            self.consume_digits();
+            is_float = true;
+        }
+
+        if matches!(self.current_char(), Some('e' | 'E')) {
+            let sign = matches!(self.peek_char(1), Some('+' | '-')) as usize;
+            if self.peek_char(1 + sign).is_some_and(|c| c.is_digit(10)) {
+                for _ in 0..=sign {
+                    self.consume();
+                }
+                self.consume_digits();
+                is_float = true;
+            }
+        }
+
+        let literal = self.slice(start.index, self.current_pos);
+        if is_float {
+            return Ok(TokenKind::Float(literal.parse().unwrap()));
+        }
+
+        // Integers are the 32-bit `int` of the generated C++, a negative literal is a negation.
+        match literal.parse::<i32>() {
+            Ok(number) => Ok(TokenKind::Number(number as i64)),
+            Err(_) => Err(IntegerOutOfRange(
+                literal.clone(),
+                TextSpan::new(start, self.pos, literal),
+                self.input.to_string(),
+            )),
+        }
+    }
+
+    fn consume_digits(&mut self) {
         while let Some(c) = self.current_char() {
-            if c.is_digit(10) {
-                self.consume().unwrap();
-                number = number * 10 + c.to_digit(10).unwrap() as i64;
-            } else {
+            if !c.is_digit(10) {
                 break;
             }
+            self.consume();
         }
-        number
     }
 
     fn update_position(&mut self, c: char) {
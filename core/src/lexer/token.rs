@@ -11,6 +11,14 @@ pub enum Keyword {
     While,
     Fn,
     Return,
+    For,
+    In,
+    Break,
+    Continue,
+    Struct,
+    Enum,
+    Match,
+    As,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -32,6 +40,9 @@ pub enum Operator {
     LessThanEquals,
     EqualsEquals,
     BangEquals,
+    Bang,
+    AmpersandAmpersand,
+    PipePipe,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -45,18 +56,27 @@ pub enum Separator {
     SemiColon,
     Arrow,
     Quote,
-    Scope
+    Scope,
+    DotDot,
+    DotDotEquals,
+    Dot,
+    FatArrow,
+    LeftBracket,
+    RightBracket,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
     Number(i64),
+    Float(f64),
     String(String),
     Operator(Operator),
     Keyword(Keyword),
     Separator(Separator),
     Bad,
     Whitespace,
+    LineComment,
+    BlockComment { terminated: bool },
     Identifier,
     Eof,
 }
@@ -65,6 +85,7 @@ impl Display for TokenKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             TokenKind::Number(_) => write!(f, "number"),
+            TokenKind::Float(_) => write!(f, "float"),
             TokenKind::String(_) => write!(f, "string"),
             TokenKind::Operator(op) => {
                 let op_str = match op {
@@ -85,6 +106,9 @@ impl Display for TokenKind {
                     Operator::LessThanEquals => "<=",
                     Operator::EqualsEquals => "==",
                     Operator::BangEquals => "!=",
+                    Operator::Bang => "!",
+                    Operator::AmpersandAmpersand => "&&",
+                    Operator::PipePipe => "||",
                 };
 
                 write!(f, "{}", op_str)
@@ -99,6 +123,14 @@ impl Display for TokenKind {
                     Keyword::While => "while",
                     Keyword::Fn => "fn",
                     Keyword::Return => "return",
+                    Keyword::For => "for",
+                    Keyword::In => "in",
+                    Keyword::Break => "break",
+                    Keyword::Continue => "continue",
+                    Keyword::Struct => "struct",
+                    Keyword::Enum => "enum",
+                    Keyword::Match => "match",
+                    Keyword::As => "as",
                 };
 
                 write!(f, "{}", kw_str)
@@ -114,13 +146,20 @@ impl Display for TokenKind {
                     Separator::SemiColon => ";",
                     Separator::Arrow => "->",
                     Separator::Quote => "\"",
-                    Separator::Scope => "::"
+                    Separator::Scope => "::",
+                    Separator::DotDot => "..",
+                    Separator::DotDotEquals => "..=",
+                    Separator::Dot => ".",
+                    Separator::FatArrow => "=>",
+                    Separator::LeftBracket => "[",
+                    Separator::RightBracket => "]",
                 };
 
                 write!(f, "{}", sep_str)
             }
             TokenKind::Bad => write!(f, "Bad"),
             TokenKind::Whitespace => write!(f, "whitespace"),
+            TokenKind::LineComment | TokenKind::BlockComment { .. } => write!(f, "comment"),
             TokenKind::Identifier => write!(f, "identifier"),
             TokenKind::Eof => write!(f, "EOF"),
         }
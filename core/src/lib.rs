@@ -8,8 +8,10 @@ pub mod build;
 mod codegen;
 pub mod error;
 mod global_context;
+pub mod interpreter;
 pub mod lexer;
 mod parser;
+pub mod repl;
 mod scopes;
 mod semantic;
 mod types;
@@ -1,14 +1,18 @@
 mod counter;
 
 use crate::ast::expr::{
-    BinOpAssociativity, BinOpKind, BinOperator, ElseBranch, Expr, ExprKind, NumberExpr, UnOpKind,
-    UnOperator,
+    BinOpAssociativity, BinOpKind, BinOperator, ElseBranch, Expr, ExprKind, FieldInitializer,
+    MatchArm, NumberExpr, Pattern, PatternBinding, UnOpKind, UnOperator, VariantPattern,
 };
-use crate::ast::function::{Body, FunctionParameter, FunctionType, TypeAnnotation};
+use crate::ast::function::{Body, Function, FunctionParameter, FunctionType, TypeAnnotation};
 use crate::ast::item::ItemKind;
+use crate::ast::span::TextSpan;
 use crate::ast::stmt::StmtKind;
+use crate::ast::structure::{FieldDeclaration, StructField, Variant, VariantDeclaration};
 use crate::ast::{item::Item, Ast, ID};
-use crate::error::error::Error::ParseError;
+use crate::error::diagnostics::Diagnostics;
+use crate::error::error::Error;
+use crate::error::error::Error::{InvalidAssignmentTarget, ParseError};
 use crate::global_context::GlobalContext;
 use crate::lexer::token::{Keyword, Operator, Separator, Token, TokenKind};
 use crate::parser::counter::Counter;
@@ -22,6 +26,13 @@ pub struct Parser<'a> {
     ast: &'a mut Ast,
     global_scope: &'a mut GlobalContext,
     content: String,
+    /// Errors that do not stop parsing, like unknown types or duplicate declarations, and
+    /// the syntax errors parsing recovered from.
+    pub diagnostics: Diagnostics,
+    /// Token position of the last syntax error reported.
+    last_error: Option<usize>,
+    /// Source offsets of struct and enum names already taken by an earlier declaration.
+    duplicate_types: Vec<usize>,
 }
 
 impl<'a> Parser<'a> {
@@ -37,6 +48,9 @@ impl<'a> Parser<'a> {
             ast,
             global_scope,
             content,
+            diagnostics: Diagnostics::new(),
+            last_error: None,
+            duplicate_types: vec![],
         }
     }
 }
@@ -44,14 +58,141 @@ impl<'a> Parser<'a> {
 impl Parser<'_> {
     pub fn parse(&mut self) -> Result<()> {
         log::debug!("Starting parsing process");
+        self.declare_types()?;
 
         while !self.is_eof() {
-            self.parse_item()?
+            if let Err(error) = self.parse_item() {
+                self.recover(error);
+                // Nothing encloses an item, so a `}` left by synchronizing closes the broken one.
+                self.consume_if(TokenKind::Separator(Separator::CloseBrace));
+            }
         }
 
         Ok(())
     }
 
+    /// Reports a syntax error and skips to a point parsing can resume from.
+    fn recover(&mut self, error: Error) {
+        self.report_syntax_error(error);
+        self.synchronize();
+    }
+
+    /// An error at the token of the previous one comes from the same mistake, like a missing
+    /// `}` seen by every body it leaves open, and is not reported again.
+    fn report_syntax_error(&mut self, error: Error) {
+        let position = self.current.get_value();
+        if self.last_error != Some(position) {
+            self.diagnostics.report(error);
+        }
+        self.last_error = Some(position);
+    }
+
+    /// Skips tokens up to the end of the broken statement: past a `;` or past a block opened
+    /// while skipping, or up to the `}` of the enclosing body or the next `fn`.
+    fn synchronize(&mut self) {
+        let mut depth = 0;
+        while !self.is_eof() {
+            match self.current().kind {
+                TokenKind::Keyword(Keyword::Fn) => return,
+                TokenKind::Separator(Separator::SemiColon) if depth == 0 => {
+                    self.consume();
+                    return;
+                }
+                TokenKind::Separator(Separator::OpenBrace) => depth += 1,
+                TokenKind::Separator(Separator::CloseBrace) => {
+                    if depth == 0 {
+                        return;
+                    }
+                    depth -= 1;
+                    if depth == 0 {
+                        self.consume();
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.consume();
+        }
+    }
+
+    /// Replaces the tokens from position `start` to the last one consumed. Like `peek`, a
+    /// position past the end stands for the last token.
+    fn error_expression(&mut self, start: usize) -> ID {
+        let first = self.tokens[start.min(self.tokens.len() - 1)].span.clone();
+        let end = if self.current.get_value() > start {
+            self.peek(-1).span.end
+        } else {
+            first.end
+        };
+        // Positions count characters, not bytes.
+        let literal = self
+            .content
+            .chars()
+            .skip(first.start.index)
+            .take(end.index.saturating_sub(first.start.index))
+            .collect();
+
+        self.ast
+            .error_expression(TextSpan::new(first.start, end, literal))
+            .id
+    }
+
+    /// Whether a loop over the statements of a body has to stop. A `fn` cannot start a
+    /// statement, it means the `}` of the body is missing.
+    fn at_body_end(&self) -> bool {
+        matches!(
+            self.current().kind,
+            TokenKind::Separator(Separator::CloseBrace)
+                | TokenKind::Keyword(Keyword::Fn)
+                | TokenKind::Eof
+        )
+    }
+
+    /// Expects the `}` closing a body. A missing one is reported and the body ends where the
+    /// next item starts, so the statements parsed so far are still checked.
+    fn check_close_brace(&mut self) -> Token {
+        let checked = self
+            .check(TokenKind::Separator(Separator::CloseBrace))
+            .cloned();
+        checked.unwrap_or_else(|error| {
+            self.report_syntax_error(error);
+            self.current().clone()
+        })
+    }
+
+    /// Registers every struct and enum name up front, so these types can be used before
+    /// their declaration.
+    fn declare_types(&mut self) -> Result<()> {
+        for window in self.tokens.windows(2) {
+            if window[1].kind != TokenKind::Identifier {
+                continue;
+            }
+            let declared = match window[0].kind {
+                TokenKind::Keyword(Keyword::Struct) => {
+                    self.global_scope.declare_struct(&window[1], &self.content)
+                }
+                TokenKind::Keyword(Keyword::Enum) => {
+                    self.global_scope.declare_enum(&window[1], &self.content)
+                }
+                _ => continue,
+            };
+            if let Err(error) = declared {
+                self.diagnostics.report(error);
+                self.duplicate_types.push(window[1].span.start.index);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves a type name, an unknown one is reported and becomes `Type::Error`.
+    fn resolve_type(&mut self, type_name: &Token) -> Type {
+        parse_type(type_name, self.global_scope, &self.content).unwrap_or_else(|error| {
+            self.diagnostics.report(error);
+            Type::Error
+        })
+    }
+
     pub fn parse_item(&mut self) -> Result<()> {
         let kind = &self.current().kind;
 
@@ -60,8 +201,16 @@ impl Parser<'_> {
                 self.parse_function()?;
                 Ok(())
             }
+            TokenKind::Keyword(Keyword::Struct) => {
+                self.parse_struct()?;
+                Ok(())
+            }
+            TokenKind::Keyword(Keyword::Enum) => {
+                self.parse_enum()?;
+                Ok(())
+            }
             _ => {
-                let statement_id = self.parse_statement()?;
+                let statement_id = self.parse_statement();
                 self.ast.new_item(ItemKind::Stmt(statement_id));
 
                 Ok(())
@@ -72,11 +221,11 @@ impl Parser<'_> {
     pub fn parse_function_type(&mut self) -> Result<Option<FunctionType>> {
         if self.current().kind == TokenKind::Separator(Separator::Arrow) {
             let arrow = self.check(TokenKind::Separator(Separator::Arrow))?.clone();
-            let return_type = self.check(TokenKind::Identifier)?;
+            let return_type = self.parse_type_name()?;
 
             return Ok(Some(FunctionType {
                 arrow,
-                type_name: return_type.clone(),
+                type_name: return_type,
             }));
         }
         Ok(None)
@@ -84,11 +233,30 @@ impl Parser<'_> {
 
     pub fn parse_type_annotation(&mut self) -> Result<TypeAnnotation> {
         let colon = self.check(TokenKind::Separator(Separator::Colon))?.clone();
-        let type_name = self.check(TokenKind::Identifier)?.clone();
+        let type_name = self.parse_type_name()?;
 
         Ok(TypeAnnotation { colon, type_name })
     }
 
+    /// Parses a type name like `int` or `[[int]]`. Array types are merged into one token, so
+    /// `parse_type` can resolve them from the literal.
+    pub fn parse_type_name(&mut self) -> Result<Token> {
+        if self.current().kind != TokenKind::Separator(Separator::LeftBracket) {
+            return Ok(self.check(TokenKind::Identifier)?.clone());
+        }
+
+        let left_bracket = self.consume().clone();
+        let element = self.parse_type_name()?;
+        let right_bracket = self
+            .check(TokenKind::Separator(Separator::RightBracket))?
+            .clone();
+
+        Ok(Token::new(
+            TokenKind::Identifier,
+            TextSpan::combine(vec![left_bracket.span, element.span, right_bracket.span]),
+        ))
+    }
+
     pub fn parse_optional_type_annotation(&mut self) -> Result<Option<TypeAnnotation>> {
         if self.current().kind == TokenKind::Separator(Separator::Colon) {
             Ok(Some(self.parse_type_annotation()?))
@@ -138,17 +306,72 @@ impl Parser<'_> {
             .while_statement(while_keyword, condition_expr, body))
     }
 
-    pub fn parse_statement(&mut self) -> Result<ID> {
-        let id = match self.current().kind {
-            TokenKind::Keyword(Keyword::Let) => self.parse_let()?,
-            TokenKind::Keyword(Keyword::While) => self.parse_while_statement()?,
-            TokenKind::Keyword(Keyword::Return) => self.parse_return_statement()?,
-            _ => self.parse_expression_statement()?,
+    pub fn parse_for_statement(&mut self) -> Result<ID> {
+        let for_keyword = self.check(TokenKind::Keyword(Keyword::For))?.clone();
+        let identifier = self.check(TokenKind::Identifier)?.clone();
+        self.check(TokenKind::Keyword(Keyword::In))?;
+        let start = self.parse_expression()?;
+
+        let inclusive = match self.current().kind {
+            TokenKind::Separator(Separator::DotDotEquals) => true,
+            _ => {
+                self.check(TokenKind::Separator(Separator::DotDot))?;
+                false
+            }
         };
+        if inclusive {
+            self.consume();
+        }
+        let end = self.parse_expression()?;
 
+        // `step` is only a keyword in this position, so it stays usable as an identifier.
+        let step = if self.current().kind == TokenKind::Identifier
+            && self.current().span.literal == "step"
+        {
+            self.consume();
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        let body = self.parse_body()?;
+        Ok(self
+            .ast
+            .for_statement(for_keyword, identifier, start, end, inclusive, step, body))
+    }
+
+    /// A statement with a syntax error is reported and becomes an expression statement of an
+    /// `ExprKind::Error`, so parsing goes on with the next one.
+    pub fn parse_statement(&mut self) -> ID {
+        let start = self.current.get_value();
+        let statement = match self.current().kind {
+            TokenKind::Keyword(Keyword::Let) => self.parse_let(),
+            TokenKind::Keyword(Keyword::While) => self.parse_while_statement(),
+            TokenKind::Keyword(Keyword::For) => self.parse_for_statement(),
+            TokenKind::Keyword(Keyword::Break) => {
+                let break_keyword = self.consume().clone();
+                Ok(self.ast.break_statement(break_keyword))
+            }
+            TokenKind::Keyword(Keyword::Continue) => {
+                let continue_keyword = self.consume().clone();
+                Ok(self.ast.continue_statement(continue_keyword))
+            }
+            TokenKind::Keyword(Keyword::Return) => self.parse_return_statement(),
+            _ => self.parse_expression_statement(),
+        };
+
+        let id = statement.unwrap_or_else(|error| {
+            self.recover(error);
+            // Only a stray `}` at the top level is left in place, skip it.
+            if self.current.get_value() == start {
+                self.consume();
+            }
+            let expr = self.error_expression(start);
+            self.ast.expression_statement(expr)
+        });
         self.consume_if(TokenKind::Separator(Separator::SemiColon));
 
-        Ok(id)
+        id
     }
 
     fn parse_expression_statement(&mut self) -> Result<ID> {
@@ -169,7 +392,27 @@ impl Parser<'_> {
             let expr = self.parse_expression()?;
             return Ok(self.ast.assignment_expression(identifier, equals, expr).id);
         }
-        self.parse_binary_expression()
+
+        let target = self.parse_binary_expression()?;
+        if self.current().kind == TokenKind::Operator(Operator::Equals) {
+            let equals = self.consume().clone();
+
+            return match self.ast.query_expr(target).kind {
+                ExprKind::FieldAccess(_) | ExprKind::Index(_) => {
+                    let expr = self.parse_expression()?;
+                    Ok(self
+                        .ast
+                        .place_assignment_expression(target, equals, expr)
+                        .id)
+                }
+                _ => Err(InvalidAssignmentTarget(
+                    self.ast.query_expr(target).span(self.ast),
+                    self.content.clone(),
+                )),
+            };
+        }
+
+        Ok(target)
     }
 
     pub fn parse_unary_operator(&mut self) -> Option<UnOperator> {
@@ -177,6 +420,7 @@ impl Parser<'_> {
         let kind = match token.kind {
             TokenKind::Operator(Operator::Minus) => Some(UnOpKind::Minus),
             TokenKind::Operator(Operator::Tilde) => Some(UnOpKind::BitwiseNot),
+            TokenKind::Operator(Operator::Bang) => Some(UnOpKind::LogicalNot),
             _ => None,
         };
         kind.map(|kind| UnOperator::new(kind, token.clone()))
@@ -205,23 +449,19 @@ impl Parser<'_> {
             .check(TokenKind::Separator(Separator::OpenBrace))?
             .clone();
         let mut body = Vec::new();
-        while self.current().kind != TokenKind::Separator(Separator::CloseBrace) && !self.is_eof() {
-            body.push(self.parse_statement()?);
+        while !self.at_body_end() {
+            body.push(self.parse_statement());
         }
-        let closing_brace = self
-            .check(TokenKind::Separator(Separator::CloseBrace))?
-            .clone();
+        let closing_brace = self.check_close_brace();
         Ok(Body::new(opening_brace, body, closing_brace))
     }
 
     fn parse_block_expression(&mut self, left_brace: Token) -> Result<&Expr> {
         let mut statements = Vec::new();
-        while self.current().kind != TokenKind::Separator(Separator::CloseBrace) && !self.is_eof() {
-            statements.push(self.parse_statement()?);
+        while !self.at_body_end() {
+            statements.push(self.parse_statement());
         }
-        let right_brace = self
-            .check(TokenKind::Separator(Separator::CloseBrace))?
-            .clone();
+        let right_brace = self.check_close_brace();
         Ok(self
             .ast
             .block_expression(left_brace, statements, right_brace))
@@ -248,11 +488,37 @@ impl Parser<'_> {
     }
 
     pub fn parse_primary_expression(&mut self) -> Result<ID> {
+        // A token that cannot start an expression, like the `;` or `}` ending the statement,
+        // is left in place for recovery.
+        let starts_expression = matches!(
+            self.current().kind,
+            TokenKind::Separator(
+                Separator::OpenBrace | Separator::LeftBracket | Separator::LeftParen
+            ) | TokenKind::Keyword(Keyword::If | Keyword::Match | Keyword::True | Keyword::False)
+                | TokenKind::Number(_)
+                | TokenKind::Float(_)
+                | TokenKind::String(_)
+                | TokenKind::Identifier
+        );
+        if !starts_expression {
+            return Err(ParseError(
+                format!(
+                    "Unexpected token: {}",
+                    self.current().kind.to_string().cyan()
+                ),
+                self.current().span.clone(),
+                self.content.clone(),
+            ));
+        }
+
         let token = self.consume().clone();
         let id = match &token.kind {
             TokenKind::Separator(Separator::OpenBrace) => self.parse_block_expression(token),
             TokenKind::Keyword(Keyword::If) => self.parse_if_expression(token),
+            TokenKind::Keyword(Keyword::Match) => return self.parse_match_expression(token),
+            TokenKind::Separator(Separator::LeftBracket) => return self.parse_array_literal(token),
             TokenKind::Number(number) => Ok(self.ast.number_expression(token.clone(), *number)),
+            TokenKind::Float(number) => Ok(self.ast.float_expression(token.clone(), *number)),
             TokenKind::String(string) => {
                 Ok(self.ast.string_expression(token.clone(), string.clone()))
             }
@@ -260,15 +526,28 @@ impl Parser<'_> {
                 let expr = self.parse_expression()?;
                 let left_paren = token;
                 let right_paren = self
-                    .check(TokenKind::Separator(Separator::LeftParen))?
+                    .check(TokenKind::Separator(Separator::RightParen))?
                     .clone();
                 self.ast
                     .parenthesized_expression(left_paren, expr, right_paren)
             }),
             TokenKind::Identifier => {
                 if self.peek(0).kind == TokenKind::Separator(Separator::Scope) {
+                    if self.global_scope.lookup_enum(&token.span.literal).is_some() {
+                        return self.parse_enum_variant(token);
+                    }
                     return self.parse_scoped_identifier(token);
                 }
+                // Only names of declared structs start a literal, so `if done { }` keeps
+                // parsing as a condition followed by a body.
+                if self.current().kind == TokenKind::Separator(Separator::OpenBrace)
+                    && self
+                        .global_scope
+                        .lookup_struct(&token.span.literal)
+                        .is_some()
+                {
+                    return self.parse_struct_literal(token);
+                }
                 if matches!(
                     self.current().kind,
                     TokenKind::Separator(Separator::LeftParen)
@@ -283,11 +562,7 @@ impl Parser<'_> {
 
                 Ok(self.ast.boolean_expression(token, value))
             }
-            _ => Err(ParseError(
-                format!("Unexpected token: {}", token.kind.to_string().cyan()),
-                token.span,
-                self.content.clone(),
-            )),
+            _ => unreachable!("Checked to start an expression"),
         }?
         .id;
 
@@ -319,13 +594,235 @@ impl Parser<'_> {
 
         Ok(self.ast.scoped_identifier(path).id)
     }
+    fn parse_array_literal(&mut self, left_bracket: Token) -> Result<ID> {
+        let mut elements = vec![];
+        while self.current().kind != TokenKind::Separator(Separator::RightBracket) && !self.is_eof()
+        {
+            elements.push(self.parse_expression()?);
+            if self.current().kind != TokenKind::Separator(Separator::RightBracket) {
+                self.check(TokenKind::Separator(Separator::Comma))?;
+            }
+        }
+        let right_bracket = self
+            .check(TokenKind::Separator(Separator::RightBracket))?
+            .clone();
+
+        Ok(self
+            .ast
+            .array_literal_expression(left_bracket, elements, right_bracket)
+            .id)
+    }
+
+    fn parse_enum_variant(&mut self, enum_name: Token) -> Result<ID> {
+        self.check(TokenKind::Separator(Separator::Scope))?;
+        let variant = self.check(TokenKind::Identifier)?.clone();
+
+        let mut arguments = vec![];
+        let mut right_paren = None;
+        if self
+            .consume_if(TokenKind::Separator(Separator::LeftParen))
+            .is_some()
+        {
+            while self.current().kind != TokenKind::Separator(Separator::RightParen)
+                && !self.is_eof()
+            {
+                arguments.push(self.parse_expression()?);
+                if self.current().kind != TokenKind::Separator(Separator::RightParen) {
+                    self.check(TokenKind::Separator(Separator::Comma))?;
+                }
+            }
+            right_paren = Some(
+                self.check(TokenKind::Separator(Separator::RightParen))?
+                    .clone(),
+            );
+        }
+
+        Ok(self
+            .ast
+            .enum_variant_expression(enum_name, variant, arguments, right_paren)
+            .id)
+    }
+
+    fn parse_match_expression(&mut self, match_keyword: Token) -> Result<ID> {
+        let scrutinee = self.parse_expression()?;
+        self.check(TokenKind::Separator(Separator::OpenBrace))?;
+
+        let mut arms = vec![];
+        while self.current().kind != TokenKind::Separator(Separator::CloseBrace) && !self.is_eof() {
+            let pattern = self.parse_pattern()?;
+            let fat_arrow = self
+                .check(TokenKind::Separator(Separator::FatArrow))?
+                .clone();
+            let body = self.parse_expression()?;
+            self.consume_if(TokenKind::Separator(Separator::Comma));
+
+            arms.push(MatchArm {
+                pattern,
+                fat_arrow,
+                body,
+            });
+        }
+        let right_brace = self
+            .check(TokenKind::Separator(Separator::CloseBrace))?
+            .clone();
+
+        Ok(self
+            .ast
+            .match_expression(match_keyword, scrutinee, arms, right_brace)
+            .id)
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern> {
+        let token = self.current().clone();
+        match &token.kind {
+            TokenKind::Identifier if token.span.literal == "_" => {
+                self.consume();
+                Ok(Pattern::Wildcard(token))
+            }
+            TokenKind::Identifier
+                if self.peek(1).kind == TokenKind::Separator(Separator::Scope) =>
+            {
+                let enum_name = self.consume().clone();
+                self.check(TokenKind::Separator(Separator::Scope))?;
+                let variant = self.check(TokenKind::Identifier)?.clone();
+
+                let mut bindings = vec![];
+                let mut right_paren = None;
+                if self
+                    .consume_if(TokenKind::Separator(Separator::LeftParen))
+                    .is_some()
+                {
+                    while self.current().kind != TokenKind::Separator(Separator::RightParen)
+                        && !self.is_eof()
+                    {
+                        bindings.push(PatternBinding {
+                            identifier: self.check(TokenKind::Identifier)?.clone(),
+                            variable_id: 0,
+                        });
+                        if self.current().kind != TokenKind::Separator(Separator::RightParen) {
+                            self.check(TokenKind::Separator(Separator::Comma))?;
+                        }
+                    }
+                    right_paren = Some(
+                        self.check(TokenKind::Separator(Separator::RightParen))?
+                            .clone(),
+                    );
+                }
+
+                Ok(Pattern::Variant(VariantPattern {
+                    enum_name,
+                    variant,
+                    bindings,
+                    right_paren,
+                }))
+            }
+            TokenKind::Number(_)
+            | TokenKind::String(_)
+            | TokenKind::Keyword(Keyword::True)
+            | TokenKind::Keyword(Keyword::False) => {
+                Ok(Pattern::Literal(self.parse_primary_expression()?))
+            }
+            TokenKind::Operator(Operator::Minus)
+                if matches!(self.peek(1).kind, TokenKind::Number(_)) =>
+            {
+                Ok(Pattern::Literal(self.parse_unary_expression()?))
+            }
+            _ => Err(ParseError(
+                format!("Expected a pattern, found {}", token.kind),
+                token.span.clone(),
+                self.content.clone(),
+            )),
+        }
+    }
+
+    fn parse_struct_literal(&mut self, identifier: Token) -> Result<ID> {
+        let left_brace = self
+            .check(TokenKind::Separator(Separator::OpenBrace))?
+            .clone();
+        let mut fields = vec![];
+        while self.current().kind != TokenKind::Separator(Separator::CloseBrace) && !self.is_eof() {
+            let field = self.check(TokenKind::Identifier)?.clone();
+            if fields
+                .iter()
+                .any(|f: &FieldInitializer| f.identifier.span.literal == field.span.literal)
+            {
+                return Err(ParseError(
+                    format!(
+                        "Field {} is initialized more than once",
+                        field.span.literal.cyan()
+                    ),
+                    field.span.clone(),
+                    self.content.clone(),
+                ));
+            }
+            self.check(TokenKind::Separator(Separator::Colon))?;
+            let value = self.parse_expression()?;
+            fields.push(FieldInitializer {
+                identifier: field,
+                value,
+            });
+
+            if self.current().kind != TokenKind::Separator(Separator::CloseBrace) {
+                self.check(TokenKind::Separator(Separator::Comma))?;
+            }
+        }
+        let right_brace = self
+            .check(TokenKind::Separator(Separator::CloseBrace))?
+            .clone();
+
+        Ok(self
+            .ast
+            .struct_literal_expression(identifier, left_brace, fields, right_brace)
+            .id)
+    }
+
+    pub fn parse_postfix_expression(&mut self) -> Result<ID> {
+        let mut expr = self.parse_primary_expression()?;
+
+        loop {
+            match self.current().kind {
+                TokenKind::Separator(Separator::Dot) => {
+                    let dot = self.consume().clone();
+                    let field = self.check(TokenKind::Identifier)?.clone();
+                    expr = self.ast.field_access_expression(expr, dot, field).id;
+                }
+                TokenKind::Separator(Separator::LeftBracket) => {
+                    let left_bracket = self.consume().clone();
+                    let index = self.parse_expression()?;
+                    let right_bracket = self
+                        .check(TokenKind::Separator(Separator::RightBracket))?
+                        .clone();
+                    expr = self
+                        .ast
+                        .index_expression(expr, left_bracket, index, right_bracket)
+                        .id;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(expr)
+    }
+
     pub fn parse_unary_expression(&mut self) -> Result<ID> {
         if let Some(operator) = self.parse_unary_operator() {
             self.consume();
             let operand = self.parse_unary_expression();
             return Ok(self.ast.unary_expr(operator, operand?).id);
         }
-        self.parse_primary_expression()
+        self.parse_postfix_expression()
+    }
+
+    /// `as` binds tighter than every binary operator, `-x as float` converts `-x`.
+    pub fn parse_cast_expression(&mut self) -> Result<ID> {
+        let mut expr = self.parse_unary_expression()?;
+        while self.current().kind == TokenKind::Keyword(Keyword::As) {
+            let as_keyword = self.consume().clone();
+            let type_name = self.parse_type_name()?;
+            expr = self.ast.cast_expression(expr, as_keyword, type_name).id;
+        }
+
+        Ok(expr)
     }
 
     fn parse_binary_operator(&mut self) -> Option<BinOperator> {
@@ -346,6 +843,8 @@ impl Parser<'_> {
             TokenKind::Operator(Operator::GreaterThan) => Some(BinOpKind::GreaterThan),
             TokenKind::Operator(Operator::GreaterThanEquals) => Some(BinOpKind::GreaterThanOrEqual),
             TokenKind::Operator(Operator::Percent) => Some(BinOpKind::Modulo),
+            TokenKind::Operator(Operator::AmpersandAmpersand) => Some(BinOpKind::LogicalAnd),
+            TokenKind::Operator(Operator::PipePipe) => Some(BinOpKind::LogicalOr),
             _ => None,
         };
         kind.map(|kind| BinOperator::new(kind, token.clone()))
@@ -358,7 +857,7 @@ impl Parser<'_> {
                 break;
             }
             self.consume();
-            let mut right = self.parse_unary_expression()?;
+            let mut right = self.parse_cast_expression()?;
 
             while let Some(inner_operator) = self.parse_binary_operator() {
                 let greater_precedence = inner_operator.precedence() > operator.precedence();
@@ -381,7 +880,7 @@ impl Parser<'_> {
     }
 
     pub fn parse_binary_expression(&mut self) -> Result<ID> {
-        let left = self.parse_unary_expression()?;
+        let left = self.parse_cast_expression()?;
         self.parse_binary_expression_recurse(left, 0)
     }
 
@@ -391,7 +890,12 @@ impl Parser<'_> {
         let type_annotation = self.parse_optional_type_annotation()?;
 
         self.check(TokenKind::Operator(Operator::Equals))?;
-        let expression = self.parse_expression()?;
+        // A malformed initializer still declares the variable, its uses are not errors.
+        let start = self.current.get_value();
+        let expression = self.parse_expression().unwrap_or_else(|error| {
+            self.recover(error);
+            self.error_expression(start)
+        });
 
         Ok(self.ast.let_stmt(indent, expression, type_annotation))
     }
@@ -408,19 +912,17 @@ impl Parser<'_> {
 
         let mut body = vec![];
 
-        while self.current().kind != TokenKind::Separator(Separator::CloseBrace) && !self.is_eof() {
-            let stmt = self.parse_statement()?;
+        while !self.at_body_end() {
+            let stmt = self.parse_statement();
 
             body.push(stmt);
         }
 
-        let close_brace = self
-            .check(TokenKind::Separator(Separator::CloseBrace))?
-            .clone();
+        let close_brace = self.check_close_brace();
 
         let mut new_params = vec![];
         for param in &params {
-            let new_type = parse_type(&param.type_annotation.type_name, &self.content)?;
+            let new_type = self.resolve_type(&param.type_annotation.type_name);
             let id = {
                 self.global_scope.add_variable(
                     param.identifier.span.literal.clone(),
@@ -435,28 +937,172 @@ impl Parser<'_> {
         let body = Body::new(open_brace, body, close_brace);
 
         let typ = match return_type {
-            Some(ref rt) => parse_type(&rt.type_name, &self.content)?,
+            Some(ref rt) => self.resolve_type(&rt.type_name),
             None => Type::Void,
         };
 
-        let func = self.global_scope.new_function(
-            identifier.clone(),
-            body.clone(),
-            new_params.clone(),
-            typ,
-            &self.content,
-        )?;
+        let func = self
+            .global_scope
+            .new_function(
+                identifier.clone(),
+                body.clone(),
+                new_params.clone(),
+                typ.clone(),
+                &self.content,
+            )
+            .unwrap_or_else(|error| {
+                self.diagnostics.report(error);
+                // Still type checked, calls resolve to the first declaration.
+                self.global_scope.push_function(Function {
+                    parameters: new_params.clone(),
+                    name: identifier.span.literal.clone(),
+                    body: body.clone(),
+                    return_type: typ,
+                })
+            });
 
         self.ast.new_func_item(
             func_keyword,
             identifier,
             new_params,
+            params.into_iter().map(|param| param.identifier).collect(),
             body,
             return_type,
             func,
         )
     }
 
+    /// `None` for a struct whose name is taken, it is parsed but not declared.
+    pub fn parse_struct(&mut self) -> Result<Option<&Item>> {
+        let struct_keyword = self.check(TokenKind::Keyword(Keyword::Struct))?.clone();
+        let identifier = self.check(TokenKind::Identifier)?.clone();
+        self.check(TokenKind::Separator(Separator::OpenBrace))?;
+
+        let mut fields: Vec<FieldDeclaration> = vec![];
+        while self.current().kind != TokenKind::Separator(Separator::CloseBrace) && !self.is_eof() {
+            let field = FieldDeclaration {
+                identifier: self.check(TokenKind::Identifier)?.clone(),
+                type_annotation: self.parse_type_annotation()?,
+            };
+
+            let name = &field.identifier.span.literal;
+            if fields.iter().any(|f| &f.identifier.span.literal == name) {
+                self.diagnostics.report(ParseError(
+                    format!("Field {} is declared more than once", name.cyan()),
+                    field.identifier.span.clone(),
+                    self.content.clone(),
+                ));
+            } else {
+                fields.push(field);
+            }
+
+            if self.current().kind != TokenKind::Separator(Separator::CloseBrace) {
+                self.check(TokenKind::Separator(Separator::Comma))?;
+            }
+        }
+        self.check(TokenKind::Separator(Separator::CloseBrace))?;
+
+        let mut struct_fields = vec![];
+        for field in &fields {
+            struct_fields.push(StructField {
+                name: field.identifier.span.literal.clone(),
+                type_: self.resolve_type(&field.type_annotation.type_name),
+            });
+        }
+
+        if self.duplicate_types.contains(&identifier.span.start.index) {
+            return Ok(None);
+        }
+        let id = self
+            .global_scope
+            .lookup_struct(&identifier.span.literal)
+            .expect("Struct was not declared before parsing");
+        self.global_scope.define_struct_fields(id, struct_fields);
+
+        Ok(Some(self.ast.new_struct_item(
+            struct_keyword,
+            identifier,
+            fields,
+            id,
+        )))
+    }
+
+    /// `None` for an enum whose name is taken, it is parsed but not declared.
+    pub fn parse_enum(&mut self) -> Result<Option<&Item>> {
+        let enum_keyword = self.check(TokenKind::Keyword(Keyword::Enum))?.clone();
+        let identifier = self.check(TokenKind::Identifier)?.clone();
+        self.check(TokenKind::Separator(Separator::OpenBrace))?;
+
+        let mut variants: Vec<VariantDeclaration> = vec![];
+        while self.current().kind != TokenKind::Separator(Separator::CloseBrace) && !self.is_eof() {
+            let variant_identifier = self.check(TokenKind::Identifier)?.clone();
+            let name = &variant_identifier.span.literal;
+            let duplicate = variants.iter().any(|v| &v.identifier.span.literal == name);
+            if duplicate {
+                self.diagnostics.report(ParseError(
+                    format!("Variant {} is declared more than once", name.cyan()),
+                    variant_identifier.span.clone(),
+                    self.content.clone(),
+                ));
+            }
+
+            let mut payload = vec![];
+            if self
+                .consume_if(TokenKind::Separator(Separator::LeftParen))
+                .is_some()
+            {
+                while self.current().kind != TokenKind::Separator(Separator::RightParen)
+                    && !self.is_eof()
+                {
+                    payload.push(self.parse_type_name()?);
+                    if self.current().kind != TokenKind::Separator(Separator::RightParen) {
+                        self.check(TokenKind::Separator(Separator::Comma))?;
+                    }
+                }
+                self.check(TokenKind::Separator(Separator::RightParen))?;
+            }
+            if !duplicate {
+                variants.push(VariantDeclaration {
+                    identifier: variant_identifier,
+                    payload,
+                });
+            }
+
+            if self.current().kind != TokenKind::Separator(Separator::CloseBrace) {
+                self.check(TokenKind::Separator(Separator::Comma))?;
+            }
+        }
+        self.check(TokenKind::Separator(Separator::CloseBrace))?;
+
+        let mut enum_variants = vec![];
+        for variant in &variants {
+            let mut payload = vec![];
+            for type_name in &variant.payload {
+                payload.push(self.resolve_type(type_name));
+            }
+            enum_variants.push(Variant {
+                name: variant.identifier.span.literal.clone(),
+                payload,
+            });
+        }
+
+        if self.duplicate_types.contains(&identifier.span.start.index) {
+            return Ok(None);
+        }
+        let id = self
+            .global_scope
+            .lookup_enum(&identifier.span.literal)
+            .expect("Enum was not declared before parsing");
+        self.global_scope.define_enum_variants(id, enum_variants);
+
+        Ok(Some(self.ast.new_enum_item(
+            enum_keyword,
+            identifier,
+            variants,
+            id,
+        )))
+    }
+
     fn is_eof(&self) -> bool {
         self.current().kind == TokenKind::Eof
     }
@@ -474,11 +1120,13 @@ impl Parser<'_> {
         }
     }
 
+    /// Consumes the current token if it is of `kind`. A wrong token is left in place, so
+    /// recovery starts from it.
     fn check(&self, kind: TokenKind) -> Result<&Token> {
-        let token = self.consume();
+        let token = self.current();
 
         if token.kind == kind {
-            Ok(token)
+            Ok(self.consume())
         } else {
             Err(ParseError(
                 format!(
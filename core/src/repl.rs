@@ -0,0 +1,140 @@
+use crate::ast::item::ItemKind;
+use crate::ast::position::Position;
+use crate::ast::stmt::StmtKind;
+use crate::ast::visitor::ASTWalker;
+use crate::ast::{Ast, ID};
+use crate::global_context::GlobalContext;
+use crate::interpreter::{Environment, Interpreter, Value};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::scopes::{LocalScope, Scopes};
+use crate::semantic::types::TypeAnalyzer;
+use crate::types::Type;
+use crate::Result;
+
+/// Incremental session behind `pulse run` without a file. Every entry is parsed into the same
+/// `Ast` and `GlobalContext`, so functions and `let` bindings stay visible to later entries.
+pub struct Repl {
+    source: String,
+    ast: Ast,
+    ctx: GlobalContext,
+    locals: Vec<LocalScope>,
+    env: Environment,
+}
+
+struct Snapshot {
+    items: usize,
+    functions: usize,
+    structs: usize,
+    enums: usize,
+    variables: usize,
+    global_variables: usize,
+    locals: Vec<LocalScope>,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Self {
+            source: String::new(),
+            ast: Ast::new(),
+            ctx: GlobalContext::new(),
+            locals: vec![LocalScope::new(None)],
+            env: Environment::new(),
+        }
+    }
+
+    /// Type checks and runs one entry. Returns the value and type of the last expression
+    /// statement, unless it is `void`. A failing entry leaves no definitions behind.
+    pub fn eval(&mut self, input: &str) -> Result<Option<(Value, Type)>> {
+        let snapshot = Snapshot {
+            items: self.ast.items.len(),
+            functions: self.ctx.functions.len(),
+            structs: self.ctx.structs.len(),
+            enums: self.ctx.enums.len(),
+            variables: self.ctx.variables.len(),
+            global_variables: self.ctx.global_variables.len(),
+            locals: self.locals.clone(),
+        };
+
+        let result = self.eval_entry(input);
+        if result.is_err() {
+            self.restore(snapshot);
+        }
+
+        result
+    }
+
+    fn eval_entry(&mut self, input: &str) -> Result<Option<(Value, Type)>> {
+        // Entries are appended to one source buffer so spans from earlier entries stay valid.
+        let start_char = self.source.chars().count();
+        let start = Position::new(self.source.matches('\n').count() as u32, 0, start_char);
+        self.source.push_str(input);
+        self.source.push('\n');
+
+        let mut lexer = Lexer::new(&self.source);
+        lexer.pos = start;
+        lexer.current_pos = start_char;
+        let tokens = lexer.tokenize()?;
+
+        let first_item = self.ast.items.len();
+        let mut parser = Parser::new(tokens, self.source.clone(), &mut self.ast, &mut self.ctx);
+        // Errors the parser recovered from come before the one that stopped it.
+        let parsed = parser.parse();
+        let mut diagnostics = std::mem::take(&mut parser.diagnostics);
+        if let Err(error) = parsed {
+            diagnostics.report(error);
+        }
+        diagnostics.into_result()?;
+        let items: Vec<ID> = self.ast.items.keys().skip(first_item).copied().collect();
+
+        let mut scopes = Scopes::new(&mut self.ctx);
+        scopes.local = std::mem::take(&mut self.locals);
+        let mut type_analyzer = TypeAnalyzer::new(self.source.clone(), scopes);
+        for id in &items {
+            if let Err(error) = type_analyzer.visit_item(&mut self.ast, *id) {
+                type_analyzer.diagnostics.report(error);
+            }
+        }
+        self.locals = std::mem::take(&mut type_analyzer.scopes.local);
+        type_analyzer.diagnostics.into_result()?;
+
+        let env = std::mem::take(&mut self.env);
+        let mut interpreter = Interpreter::new(&self.ctx, self.source.clone(), env);
+        let mut output = None;
+        let mut executed = Ok(());
+
+        for id in items {
+            if let Err(e) = interpreter.visit_item(&mut self.ast, id) {
+                executed = Err(e);
+                break;
+            }
+
+            output = match &self.ast.query_item(id).kind {
+                ItemKind::Stmt(stmt) => match &self.ast.query_stmt(*stmt).kind {
+                    StmtKind::Expr(expr) => Some((
+                        interpreter.value.clone(),
+                        self.ast.query_expr(*expr).ty.clone(),
+                    )),
+                    _ => None,
+                },
+                _ => None,
+            };
+        }
+        self.env = interpreter.env;
+        executed?;
+
+        Ok(output.filter(|(_, ty)| !matches!(ty, Type::Void)))
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.ast.items.truncate(snapshot.items);
+        self.ctx.functions.truncate(snapshot.functions);
+        self.ctx.structs.truncate(snapshot.structs);
+        self.ctx.enums.truncate(snapshot.enums);
+        self.ctx.variables.truncate(snapshot.variables);
+        self.ctx
+            .global_variables
+            .truncate(snapshot.global_variables);
+        self.locals = snapshot.locals;
+    }
+}
@@ -57,14 +57,17 @@ impl<'a> Scopes<'a> {
     pub fn new_var(&mut self, name: String, type_: Type) -> ID {
         let is_global = self.in_scope();
         let id = {
-            let shadowing = if let Some(scope) = self.local.last_mut() {
-                scope.locals.iter().any(|local| {
-                    let var = self.global.variables.get(local).unwrap();
-                    var.name == name
-                })
-            } else {
-                false
-            };
+            // Any enclosing scope counts, a nested `let x = x + 1` shadows too, and so does a
+            // top-level variable.
+            let shadowing = self.global.get_global_variable(&name).is_some()
+                || self
+                    .local
+                    .iter()
+                    .flat_map(|scope| &scope.locals)
+                    .any(|local| {
+                        let var = self.global.variables.get(local).unwrap();
+                        var.name == name
+                    });
 
             self.global.add_variable(name, type_, shadowing, !is_global)
         };
@@ -89,12 +92,23 @@ impl<'a> Scopes<'a> {
         self.global.lookup_var_id(name)
     }
 
+    /// Names of the variables `lookup_var` can find.
+    pub fn visible_variables(&self) -> Vec<&str> {
+        let locals = self.local.iter().flat_map(|scope| &scope.locals);
+
+        locals
+            .chain(&self.global.global_variables)
+            .filter_map(|id| self.global.variables.get(id))
+            .map(|var| var.name.as_str())
+            .collect()
+    }
+
     pub fn current_function(&self) -> Option<&Function> {
-        self.local
-            .iter()
-            .rev()
-            .filter_map(|scope| scope.function)
-            .next()
+        self.current_function_id()
             .and_then(|function| self.global.functions.get(&function))
     }
+
+    pub fn current_function_id(&self) -> Option<ID> {
+        self.local.iter().rev().find_map(|scope| scope.function)
+    }
 }
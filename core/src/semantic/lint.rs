@@ -0,0 +1,276 @@
+use crate::ast::expr::{
+    BlockExpr, BoolExpr, CallExpr, Expr, ExprKind, FloatExpr, IfExpr, NumberExpr, StringExpr,
+    UnaryExpr, VarExpr,
+};
+use crate::ast::function::{Body, FunctionDeclaration};
+use crate::ast::item::ItemKind;
+use crate::ast::span::TextSpan;
+use crate::ast::stmt::{LetStmt, Stmt, StmtKind, WhileStmt};
+use crate::ast::visitor::ASTWalker;
+use crate::ast::{Ast, ID};
+use crate::error::diagnostics::Diagnostics;
+use crate::error::warning::Warning::{
+    LoopNeverRuns, UnreachableCode, UnusedFunction, UnusedVariable,
+};
+use crate::global_context::GlobalContext;
+use crate::lexer::token::Token;
+use crate::Result;
+use std::collections::{HashMap, HashSet};
+
+/// Looks for code that type checks but is most likely a mistake: unused variables and
+/// functions, statements after a `return` and loops that never run. Runs on a program without
+/// errors and only reports warnings.
+pub struct Linter<'a> {
+    content: String,
+    ctx: &'a GlobalContext,
+    pub diagnostics: Diagnostics,
+    /// `let` variables and parameters, with the identifier declaring them.
+    declared_variables: Vec<(ID, Token)>,
+    used_variables: HashSet<ID>,
+    /// Functions called from each function, `None` stands for the top-level statements.
+    calls: HashMap<Option<ID>, Vec<ID>>,
+    current_function: Option<ID>,
+}
+
+impl<'a> Linter<'a> {
+    pub fn new(content: String, ctx: &'a GlobalContext) -> Self {
+        Self {
+            content,
+            ctx,
+            diagnostics: Diagnostics::new(),
+            declared_variables: vec![],
+            used_variables: HashSet::new(),
+            calls: HashMap::new(),
+            current_function: None,
+        }
+    }
+
+    pub fn lint(&mut self, ast: &mut Ast) {
+        for id in ast.items.keys().copied().collect::<Vec<ID>>() {
+            self.visit_item(ast, id).expect("Linting does not fail");
+        }
+
+        self.report_unused_variables();
+        self.report_unused_functions(ast);
+    }
+
+    fn report_unused_variables(&mut self) {
+        for (id, identifier) in &self.declared_variables {
+            let name = &identifier.span.literal;
+            if self.used_variables.contains(id) || name.starts_with('_') {
+                continue;
+            }
+
+            self.diagnostics.warn(UnusedVariable(
+                name.clone(),
+                identifier.span.clone(),
+                self.content.clone(),
+            ));
+        }
+    }
+
+    /// Functions are used when `main` or a top-level statement reaches them through calls.
+    fn report_unused_functions(&mut self, ast: &Ast) {
+        let mut reached: HashSet<ID> = HashSet::new();
+        let mut pending: Vec<Option<ID>> = vec![None];
+        if let Some(main) = self.ctx.lookup_function("main") {
+            reached.insert(main);
+            pending.push(Some(main));
+        }
+
+        while let Some(caller) = pending.pop() {
+            for callee in self.calls.get(&caller).into_iter().flatten() {
+                if reached.insert(*callee) {
+                    pending.push(Some(*callee));
+                }
+            }
+        }
+
+        for item in ast.items.values() {
+            let ItemKind::Function(func_decl) = &item.kind else {
+                continue;
+            };
+            if reached.contains(&func_decl.id) {
+                continue;
+            }
+
+            self.diagnostics.warn(UnusedFunction(
+                func_decl.identifier.span.literal.clone(),
+                func_decl.identifier.span.clone(),
+                self.content.clone(),
+            ));
+        }
+    }
+
+    /// Visits the statements of a body, warning about the first one following a `return`.
+    fn visit_statements(&mut self, ast: &mut Ast, stmts: &[ID]) -> Result<()> {
+        let return_statement = stmts
+            .iter()
+            .position(|stmt| matches!(ast.query_stmt(*stmt).kind, StmtKind::Return(_)));
+        if let Some(unreachable) = return_statement.and_then(|index| stmts.get(index + 1)) {
+            let span = ast.query_stmt(*unreachable).span(ast);
+            self.diagnostics
+                .warn(UnreachableCode(span, self.content.clone()));
+        }
+
+        for stmt in stmts {
+            self.visit_statement(ast, *stmt)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> ASTWalker for Linter<'a> {
+    fn visit_body(&mut self, ast: &mut Ast, body: &Body) -> Result<()> {
+        self.visit_statements(ast, &body.stmts)
+    }
+
+    fn visit_func_decl(
+        &mut self,
+        ast: &mut Ast,
+        func_decl: &FunctionDeclaration,
+        _item_id: ID,
+    ) -> Result<()> {
+        for (param, identifier) in func_decl
+            .parameters
+            .iter()
+            .zip(&func_decl.parameter_identifiers)
+        {
+            self.declared_variables.push((*param, identifier.clone()));
+        }
+
+        self.current_function = Some(func_decl.id);
+        self.visit_body(ast, &func_decl.body)?;
+        self.current_function = None;
+
+        Ok(())
+    }
+
+    fn visit_while_statement(&mut self, ast: &mut Ast, while_statement: &WhileStmt) -> Result<()> {
+        let condition = ast.query_expr(while_statement.condition);
+        if let ExprKind::Boolean(BoolExpr { value: false, .. }) = condition.kind {
+            self.diagnostics.warn(LoopNeverRuns(
+                while_statement.while_keyword.span.clone(),
+                self.content.clone(),
+            ));
+        }
+
+        self.visit_expression(ast, while_statement.condition)?;
+        self.visit_body(ast, &while_statement.body)
+    }
+
+    fn visit_block_expr(
+        &mut self,
+        ast: &mut Ast,
+        block_expr: &BlockExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_statements(ast, &block_expr.stmts)
+    }
+
+    fn visit_if_expression(&mut self, ast: &mut Ast, if_expr: &IfExpr, _expr: &Expr) -> Result<()> {
+        self.visit_expression(ast, if_expr.condition)?;
+        self.visit_body(ast, &if_expr.then_branch)?;
+        if let Some(else_branch) = &if_expr.else_branch {
+            self.visit_body(ast, &else_branch.body)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_let_statement(
+        &mut self,
+        ast: &mut Ast,
+        let_statement: &LetStmt,
+        _stmt: &Stmt,
+    ) -> Result<()> {
+        self.declared_variables
+            .push((let_statement.variable_id, let_statement.identifier.clone()));
+
+        self.visit_expression(ast, let_statement.initializer)
+    }
+
+    fn visit_call_expression(
+        &mut self,
+        ast: &mut Ast,
+        call_expression: &CallExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        // Scoped calls go to the standard library.
+        if call_expression.scope.is_none() {
+            if let Some(function) = self.ctx.lookup_function(call_expression.function_name()) {
+                self.calls
+                    .entry(self.current_function)
+                    .or_default()
+                    .push(function);
+            }
+        }
+
+        for argument in &call_expression.arguments {
+            self.visit_expression(ast, *argument)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_variable_expression(
+        &mut self,
+        _ast: &mut Ast,
+        variable_expression: &VarExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.used_variables.insert(variable_expression.variable_idx);
+
+        Ok(())
+    }
+
+    fn visit_number_expression(
+        &mut self,
+        _ast: &mut Ast,
+        _number: &NumberExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_float_expression(
+        &mut self,
+        _ast: &mut Ast,
+        _number: &FloatExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_string_expression(
+        &mut self,
+        _ast: &mut Ast,
+        _string: &StringExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_boolean_expression(
+        &mut self,
+        _ast: &mut Ast,
+        _boolean: &BoolExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_error(&mut self, _ast: &mut Ast, _span: &TextSpan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_unary_expression(
+        &mut self,
+        ast: &mut Ast,
+        unary_expression: &UnaryExpr,
+        _expr: &Expr,
+    ) -> Result<()> {
+        self.visit_expression(ast, unary_expression.operand)
+    }
+}
@@ -1 +1,3 @@
+pub mod lint;
+pub mod suggest;
 pub mod types;
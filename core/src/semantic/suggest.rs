@@ -0,0 +1,53 @@
+/// The suggestion of the candidate closest to `name`, if one is close enough to be what was
+/// meant. Candidates pair the name compared with `name` and the text suggested for it, like
+/// `println` and `std::io::println`.
+pub fn closest_match<S: AsRef<str>>(
+    name: &str,
+    candidates: impl IntoIterator<Item = (S, String)>,
+) -> Option<String> {
+    // One edit turns a one-character name into any other, so nothing is close to it.
+    let length = name.chars().count();
+    if length < 2 {
+        return None;
+    }
+    // Allows about one typo every three characters, like rustc.
+    let max_distance = (length / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|(candidate, suggestion)| (edit_distance(name, candidate.as_ref()), suggestion))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, suggestion)| suggestion)
+}
+
+/// Number of characters to insert, remove or replace, or of adjacent pairs to swap, to turn
+/// `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // `distances[i][j]` is the distance between the first `i` characters of `a` and the first
+    // `j` of `b`.
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=b.len() {
+        distances[0][j] = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
@@ -1,56 +1,601 @@
 use crate::ast::expr::{
-    combine_call_expr_span, AssignExpr, BinOpKind, BinaryExpr, BlockExpr, BoolExpr, CallExpr, Expr,
-    ExprKind, IfExpr, NumberExpr, StringExpr, UnOpKind, UnaryExpr, VarExpr,
+    combine_call_expr_span, ArrayLiteralExpr, AssignExpr, BinOpKind, BinaryExpr, BlockExpr,
+    BoolExpr, CallExpr, CastExpr, EnumVariantExpr, Expr, ExprKind, FieldAccessExpr, FloatExpr,
+    IfExpr, IndexExpr, MatchExpr, NumberExpr, ParenthesizedExpr, Pattern, PlaceAssignExpr,
+    StringExpr, StructLiteralExpr, UnOpKind, UnaryExpr, VarExpr,
 };
-use crate::ast::function::{get_type_of_last_expr, FunctionDeclaration, TypeAnnotation};
+use crate::ast::function::{get_type_of_last_expr, Body, FunctionDeclaration, TypeAnnotation};
 use crate::ast::item::ItemKind;
 use crate::ast::span::TextSpan;
-use crate::ast::stmt::{LetStmt, ReturnStmt, Stmt, WhileStmt};
+use crate::ast::stmt::{
+    BreakStmt, ContinueStmt, ForStmt, LetStmt, ReturnStmt, Stmt, StmtKind, WhileStmt,
+};
+use crate::ast::structure::{EnumDeclaration, StructDeclaration};
 use crate::ast::visitor::ASTWalker;
 use crate::ast::{Ast, ID};
+use crate::error::diagnostics::Diagnostics;
 use crate::error::error::Error::{
-    CallToUndeclaredFunction, IllegalReturn, InvalidArguments, MainFunctionParameters, NotFound,
-    ReservedName, TypeMismatch,
+    CallToUndeclaredFunction, CannotInferType, IllegalReturn, InvalidArguments,
+    InvalidAssignmentTarget, InvalidCast, MainFunctionParameters, MissingFields, MissingMain,
+    MissingReturn, NonExhaustiveMatch, NotFound, OutsideLoop, RecursiveType, ReservedName,
+    StatementOutsideMain, TypeMismatch, UninitializedGlobal, UnknownField, UnknownVariant,
 };
-use crate::lexer::token::{Operator, TokenKind};
+use crate::lexer::token::{Operator, Token, TokenKind};
 use crate::scopes::Scopes;
+use crate::semantic::suggest::closest_match;
 use crate::types::{parse_type, Type};
 use crate::Result;
 use indexmap::IndexMap;
 use lazy_static::lazy_static;
+use std::collections::HashMap;
 use std::process::id;
 
 pub struct TypeAnalyzer<'a> {
     pub content: String,
     pub scopes: Scopes<'a>,
+    /// Every error found so far. A failed expression is reported here and typed
+    /// `Type::Error`, which is assignable to anything, so it does not cause more errors.
+    pub diagnostics: Diagnostics,
+    loop_depth: usize,
+    /// Top-level variables each function uses, and the functions it calls.
+    global_uses: HashMap<ID, Vec<ID>>,
+    calls: HashMap<ID, Vec<ID>>,
+    /// Calls made by top-level statements, with how many top-level variables are initialized
+    /// when they run. See `check_global_initialization`.
+    top_level_calls: Vec<(ID, TextSpan, usize)>,
+}
+
+impl<'a> TypeAnalyzer<'a> {
+    pub fn new(content: String, scopes: Scopes<'a>) -> Self {
+        Self {
+            content,
+            scopes,
+            diagnostics: Diagnostics::new(),
+            loop_depth: 0,
+            global_uses: HashMap::new(),
+            calls: HashMap::new(),
+            top_level_calls: vec![],
+        }
+    }
+
+    /// Records a use of `variable` by the current function, if it is a top-level variable.
+    fn record_global_use(&mut self, variable: ID) {
+        if !self.scopes.global.global_variables.contains(&variable) {
+            return;
+        }
+        if let Some(function) = self.scopes.current_function_id() {
+            self.global_uses.entry(function).or_default().push(variable);
+        }
+    }
+
+    /// Top-level variables are initialized in order, a top-level statement cannot call a
+    /// function that uses one declared at or after it.
+    fn check_global_initialization(&mut self) {
+        for (callee, span, initialized) in std::mem::take(&mut self.top_level_calls) {
+            let mut reached = vec![callee];
+            let mut pending = vec![callee];
+            while let Some(function) = pending.pop() {
+                for called in self.calls.get(&function).into_iter().flatten() {
+                    if !reached.contains(called) {
+                        reached.push(*called);
+                        pending.push(*called);
+                    }
+                }
+            }
+
+            let globals = &self.scopes.global.global_variables;
+            let uninitialized = reached.iter().find_map(|function| {
+                self.global_uses
+                    .get(function)
+                    .into_iter()
+                    .flatten()
+                    .find(|variable| {
+                        globals
+                            .iter()
+                            .position(|global| global == *variable)
+                            .is_some_and(|position| position >= initialized)
+                    })
+            });
+            if let Some(variable) = uninitialized {
+                let ctx = &self.scopes.global;
+                self.diagnostics.report(UninitializedGlobal(
+                    ctx.variables[variable].name.clone(),
+                    ctx.functions[&callee].name.clone(),
+                    span,
+                    self.content.clone(),
+                ));
+            }
+        }
+    }
+
+    /// A visible variable whose name is close to `name`.
+    fn suggest_variable(&self, name: &str) -> Option<String> {
+        let variables = self.scopes.visible_variables();
+
+        closest_match(
+            name,
+            variables
+                .into_iter()
+                .map(|variable| (variable, variable.to_string())),
+        )
+    }
+
+    /// Reports the error of a check whose failure does not change the type being computed.
+    fn report(&mut self, result: Result<Type>) {
+        if let Err(error) = result {
+            self.diagnostics.report(error);
+        }
+    }
+
+    /// Type checks a whole program. Top-level `let`s declare globals, initialized in order
+    /// before `main` runs. Any other top-level statement makes the file a script, whose
+    /// top-level statements are the body of its `main`, so it cannot define `fn main` itself.
+    /// Errors are collected in `diagnostics`.
+    pub fn analyze(&mut self, ast: &mut Ast) {
+        for id in ast.items.keys().copied().collect::<Vec<ID>>() {
+            if let Err(error) = self.visit_item(ast, id) {
+                self.diagnostics.report(error);
+            }
+        }
+        self.check_global_initialization();
+
+        let Some(main) = self.scopes.global.lookup_function("main") else {
+            let is_script = ast
+                .items
+                .values()
+                .any(|item| matches!(item.kind, ItemKind::Stmt(_)));
+            if !is_script {
+                self.diagnostics.report(MissingMain);
+            }
+            return;
+        };
+        if let Err(error) = self.expect_main_signature(ast, main) {
+            self.diagnostics.report(error);
+        }
+
+        for item in ast.items.values() {
+            let ItemKind::Stmt(stmt) = item.kind else {
+                continue;
+            };
+            let statement = ast.query_stmt(stmt);
+            match &statement.kind {
+                StmtKind::Let(_) => continue,
+                // Reported as a syntax error already.
+                StmtKind::Expr(expr)
+                    if matches!(ast.query_expr(*expr).kind, ExprKind::Error(_)) =>
+                {
+                    continue
+                }
+                _ => {}
+            }
+
+            let span = statement.span(ast);
+            self.diagnostics
+                .report(StatementOutsideMain(span, self.content.clone()));
+        }
+    }
+
+    /// `main` is `fn main()`, `fn main() -> int` or `fn main(args: [string]) -> int`. The
+    /// returned int is the exit status of the program.
+    fn expect_main_signature(&self, ast: &Ast, main: ID) -> Result<()> {
+        let func = &self.scopes.global.functions[&main];
+        let parameters: Vec<&Type> = func
+            .parameters
+            .iter()
+            .map(|param| &self.scopes.global.variables[param].type_)
+            .collect();
+
+        let valid = match (parameters.as_slice(), &func.return_type) {
+            ([], Type::Void | Type::Int) => true,
+            ([Type::Array(element)], Type::Int) => matches!(**element, Type::String),
+            _ => false,
+        };
+        if valid {
+            return Ok(());
+        }
+
+        let span = ast
+            .items
+            .values()
+            .find_map(|item| match &item.kind {
+                ItemKind::Function(func_decl) if func_decl.id == main => {
+                    Some(func_decl.identifier.span.clone())
+                }
+                _ => None,
+            })
+            .expect("main has no declaration");
+
+        Err(MainFunctionParameters(span, self.content.clone()))
+    }
+
+    fn visit_loop_body(&mut self, ast: &mut Ast, body: &Body) -> Result<()> {
+        self.loop_depth += 1;
+        let result = self.visit_body(ast, body);
+        self.loop_depth -= 1;
+
+        result
+    }
+
+    fn expect_in_loop(&self, keyword: &Token) -> Result<()> {
+        if self.loop_depth == 0 {
+            return Err(OutsideLoop(
+                keyword.span.literal.clone(),
+                keyword.span.clone(),
+                self.content.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if `name` can be reached by following struct fields and enum payloads
+    /// from `from`. Such a type would have infinite size.
+    fn type_contains(&self, from: &str, name: &str, seen: &mut Vec<String>) -> bool {
+        if seen.iter().any(|s| s == from) {
+            return false;
+        }
+        seen.push(from.to_string());
+
+        let members: Vec<Type> = if let Some(structure) = self.scopes.global.get_struct(from) {
+            structure.fields.iter().map(|f| f.type_.clone()).collect()
+        } else if let Some(enumeration) = self.scopes.global.get_enum(from) {
+            enumeration
+                .variants
+                .iter()
+                .flat_map(|v| v.payload.clone())
+                .collect()
+        } else {
+            return false;
+        };
+
+        members.iter().any(|member| match member {
+            Type::Struct(inner) | Type::Enum(inner) => {
+                inner == name || self.type_contains(inner, name, seen)
+            }
+            _ => false,
+        })
+    }
+
+    fn expect_non_recursive(&self, identifier: &Token) -> Result<()> {
+        let name = &identifier.span.literal;
+        if self.type_contains(name, name, &mut vec![]) {
+            return Err(RecursiveType(
+                name.clone(),
+                identifier.span.clone(),
+                self.content.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks a pattern against the type of the matched value and declares its bindings in
+    /// the current scope.
+    fn visit_pattern(
+        &mut self,
+        ast: &mut Ast,
+        pattern: &Pattern,
+        scrutinee: &Type,
+        expr: ID,
+        arm: usize,
+    ) -> Result<()> {
+        match pattern {
+            Pattern::Wildcard(_) => Ok(()),
+            Pattern::Literal(literal) => {
+                self.visit_expression(ast, *literal)?;
+                let literal = ast.query_expr(*literal);
+                expect_type(&literal.ty, scrutinee, &literal.span(ast), &self.content)?;
+
+                Ok(())
+            }
+            Pattern::Variant(variant_pattern) => {
+                let enum_name = variant_pattern.enum_name.span.literal.clone();
+                let enum_type = expect_type(
+                    &Type::Enum(enum_name.clone()),
+                    scrutinee,
+                    &pattern.span(ast),
+                    &self.content,
+                );
+                self.report(enum_type);
+
+                let variant = self
+                    .scopes
+                    .global
+                    .get_enum(&enum_name)
+                    .and_then(|enumeration| enumeration.variant(variant_pattern.variant_name()))
+                    .cloned()
+                    .ok_or_else(|| {
+                        UnknownVariant(
+                            variant_pattern.variant_name().to_string(),
+                            enum_name.clone(),
+                            variant_pattern.variant.span.clone(),
+                            self.content.clone(),
+                        )
+                    });
+
+                // Bindings of a wrong pattern are still declared, as `Type::Error`, so the arm
+                // body is checked without errors about them.
+                let payload = match variant {
+                    Ok(variant) if variant.payload.len() == variant_pattern.bindings.len() => {
+                        Ok(variant.payload)
+                    }
+                    Ok(variant) => Err(InvalidArguments(
+                        variant.payload.len(),
+                        variant_pattern.bindings.len(),
+                        pattern.span(ast),
+                        self.content.clone(),
+                    )),
+                    Err(error) => Err(error),
+                };
+                let types = match &payload {
+                    Ok(payload) => payload.clone(),
+                    Err(_) => vec![Type::Error; variant_pattern.bindings.len()],
+                };
+
+                for (index, (binding, type_)) in
+                    variant_pattern.bindings.iter().zip(types).enumerate()
+                {
+                    if binding.is_wildcard() {
+                        continue;
+                    }
+                    let var = self
+                        .scopes
+                        .new_var(binding.identifier.span.literal.clone(), type_);
+                    ast.set_pattern_binding(expr, arm, index, var);
+                }
+
+                payload.map(|_| ())
+            }
+        }
+    }
+
+    /// A function with a return type either ends with an expression of that type, which is
+    /// returned implicitly, or returns on every path.
+    fn expect_return(&self, ast: &Ast, func_decl: &FunctionDeclaration) -> Result<()> {
+        let func = self.scopes.global.functions.get(&func_decl.id).unwrap();
+        if matches!(func.return_type, Type::Void) {
+            return Ok(());
+        }
+
+        let stmts = &func_decl.body.stmts;
+        if let Some(StmtKind::Expr(trailing)) = stmts.last().map(|s| &ast.query_stmt(*s).kind) {
+            let trailing = ast.query_expr(*trailing);
+            if !matches!(trailing.ty, Type::Void) {
+                expect_type(
+                    &trailing.ty,
+                    &func.return_type,
+                    &trailing.span(ast),
+                    &self.content,
+                )?;
+                return Ok(());
+            }
+        }
+
+        if always_returns(ast, stmts) {
+            return Ok(());
+        }
+
+        Err(MissingReturn(
+            func.name.clone(),
+            func.return_type.to_str(),
+            func_decl.body.closing_brace.span.clone(),
+            self.content.clone(),
+        ))
+    }
+
+    /// Only fields and elements reachable from a variable are places, `make().x = 1` has
+    /// nowhere to store the value.
+    fn expect_place(&self, ast: &Ast, place: ID) -> Result<()> {
+        let mut root = ast.query_expr(place);
+        loop {
+            match &root.kind {
+                ExprKind::FieldAccess(access) => root = ast.query_expr(access.object),
+                ExprKind::Index(index) => root = ast.query_expr(index.object),
+                ExprKind::Parenthesized(inner) => root = ast.query_expr(inner.inner),
+                ExprKind::Variable(_) => return Ok(()),
+                _ => {
+                    return Err(InvalidAssignmentTarget(
+                        ast.query_expr(place).span(ast),
+                        self.content.clone(),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn expect_std_arguments(
+        &self,
+        ast: &Ast,
+        call_expression: &CallExpr,
+        function: &StdFunction,
+    ) -> Result<()> {
+        let Some(parameters) = &function.parameters else {
+            return Ok(());
+        };
+        if call_expression.arguments.len() != parameters.len() {
+            return Err(InvalidArguments(
+                parameters.len(),
+                call_expression.arguments.len(),
+                combine_call_expr_span(call_expression),
+                self.content.clone(),
+            ));
+        }
+        for (argument, parameter) in call_expression.arguments.iter().zip(parameters) {
+            let argument = ast.query_expr(*argument);
+            expect_type(&argument.ty, parameter, &argument.span(ast), &self.content)?;
+        }
+
+        Ok(())
+    }
+
+    /// `std::array` functions are generic over the element type, so their signatures are
+    /// checked here instead of through `STD_MODULES`.
+    fn array_call_type(&self, ast: &Ast, call_expression: &CallExpr) -> Result<Type> {
+        let name = &call_expression.callee.span.literal[..];
+        let expected_args = if name == "push" { 2 } else { 1 };
+        if call_expression.arguments.len() != expected_args {
+            return Err(InvalidArguments(
+                expected_args,
+                call_expression.arguments.len(),
+                combine_call_expr_span(call_expression),
+                self.content.clone(),
+            ));
+        }
+
+        let array = ast.query_expr(call_expression.arguments[0]);
+        let element = match &array.ty {
+            Type::Array(element) => *element.clone(),
+            Type::Error => Type::Error,
+            other => {
+                return Err(TypeMismatch(
+                    other.to_str(),
+                    "array".to_string(),
+                    array.span(ast),
+                    self.content.clone(),
+                ))
+            }
+        };
+
+        match name {
+            "len" => Ok(Type::Int),
+            "push" => {
+                self.expect_place(ast, call_expression.arguments[0])?;
+                let value = ast.query_expr(call_expression.arguments[1]);
+                expect_type(&value.ty, &element, &value.span(ast), &self.content)?;
+                Ok(Type::Void)
+            }
+            "pop" => {
+                self.expect_place(ast, call_expression.arguments[0])?;
+                Ok(element)
+            }
+            _ => unreachable!("Unknown array function {}", name),
+        }
+    }
+
+    /// Lists the values of `scrutinee` that no arm of the match covers.
+    fn missing_patterns(&self, ast: &Ast, match_expr: &MatchExpr, scrutinee: &Type) -> Vec<String> {
+        if match_expr
+            .arms
+            .iter()
+            .any(|arm| matches!(arm.pattern, Pattern::Wildcard(_)))
+        {
+            return vec![];
+        }
+
+        match scrutinee {
+            Type::Enum(name) => {
+                let enumeration = self.scopes.global.get_enum(name).unwrap();
+                enumeration
+                    .variants
+                    .iter()
+                    .filter(|variant| {
+                        !match_expr.arms.iter().any(|arm| match &arm.pattern {
+                            Pattern::Variant(pattern) => pattern.variant_name() == variant.name,
+                            _ => false,
+                        })
+                    })
+                    .map(|variant| format!("{}::{}", name, variant.name))
+                    .collect()
+            }
+            Type::Bool => [true, false]
+                .into_iter()
+                .filter(|value| {
+                    !match_expr.arms.iter().any(|arm| match &arm.pattern {
+                        Pattern::Literal(literal) => matches!(
+                            &ast.query_expr(*literal).kind,
+                            ExprKind::Boolean(boolean) if boolean.value == *value
+                        ),
+                        _ => false,
+                    })
+                })
+                .map(|value| value.to_string())
+                .collect(),
+            Type::Error => vec![],
+            _ => vec!["_".to_string()],
+        }
+    }
+}
+
+/// Signature of a function from the bundled std, `parameters` is `None` for functions that
+/// accept any arguments like `io::println`.
+pub struct StdFunction {
+    pub parameters: Option<Vec<Type>>,
+    pub return_type: Type,
+}
+
+impl StdFunction {
+    fn new(parameters: Vec<Type>, return_type: Type) -> Self {
+        Self {
+            parameters: Some(parameters),
+            return_type,
+        }
+    }
+
+    fn variadic(return_type: Type) -> Self {
+        Self {
+            parameters: None,
+            return_type,
+        }
+    }
+}
+
+/// Every function of the bundled std, with the module it is in.
+fn std_functions() -> impl Iterator<Item = (&'static str, &'static str)> {
+    STD_MODULES
+        .iter()
+        .flat_map(|(module, functions)| functions.keys().map(move |function| (*module, *function)))
 }
 
 lazy_static! {
     pub static ref STD_RESERVED_WORDS: Vec<&'static str> =
         vec!["print", "println", "eprintln", "eprint"];
-    pub static ref STD_MODULES: IndexMap<&'static str, IndexMap<&'static str, Type>> = {
+    pub static ref STD_MODULES: IndexMap<&'static str, IndexMap<&'static str, StdFunction>> = {
         let mut map = IndexMap::new();
         let mut io = IndexMap::new();
-        io.insert("print", Type::Void);
-        io.insert("println", Type::Void);
-        io.insert("eprint", Type::Void);
-        io.insert("eprintln", Type::Void);
+        io.insert("print", StdFunction::variadic(Type::Void));
+        io.insert("println", StdFunction::variadic(Type::Void));
+        io.insert("eprint", StdFunction::variadic(Type::Void));
+        io.insert("eprintln", StdFunction::variadic(Type::Void));
         map.insert("io", io);
 
         let mut math = IndexMap::new();
-        math.insert("sqrt", Type::Int);
+        math.insert("sqrt", StdFunction::new(vec![Type::Float], Type::Float));
         map.insert("math", math);
 
         let mut env = IndexMap::new();
-        env.insert("get_cwd", Type::String);
-        env.insert("get_home", Type::String);
+        env.insert("get_cwd", StdFunction::new(vec![], Type::String));
+        env.insert("get_home", StdFunction::new(vec![], Type::String));
         map.insert("env", env);
 
+        // Generic over the element type, see `TypeAnalyzer::array_call_type`.
+        let mut array = IndexMap::new();
+        array.insert("len", StdFunction::variadic(Type::Int));
+        array.insert("push", StdFunction::variadic(Type::Void));
+        array.insert("pop", StdFunction::variadic(Type::Unresolved));
+        map.insert("array", array);
+
         map
     };
 }
 
 impl<'a> ASTWalker for TypeAnalyzer<'a> {
+    fn visit_statement(&mut self, ast: &mut Ast, statement: ID) -> Result<()> {
+        if let Err(error) = self.do_visit_statement(ast, statement) {
+            self.diagnostics.report(error);
+        }
+
+        Ok(())
+    }
+
+    fn visit_expression(&mut self, ast: &mut Ast, expression: ID) -> Result<()> {
+        if let Err(error) = self.do_visit_expression(ast, expression) {
+            self.diagnostics.report(error);
+            ast.update_type(expression, Type::Error);
+        }
+
+        Ok(())
+    }
+
     fn visit_func_decl(
         &mut self,
         ast: &mut Ast,
@@ -61,7 +606,6 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
             "TypeAnalyzer::visit_func_decl func with id: {}",
             func_decl.id
         );
-        self.scopes.push_scope(Some(func_decl.id));
         let func = self.scopes.global.functions.get(&func_decl.id).unwrap();
         if STD_RESERVED_WORDS.contains(&&*func.name) {
             let item = ast.query_item(item_id);
@@ -70,17 +614,32 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
                 _ => unreachable!(),
             };
 
-            return Err(ReservedName(func.name.clone(), span, self.content.clone()));
+            let error = ReservedName(func.name.clone(), span, self.content.clone());
+            self.diagnostics.report(error);
         }
 
+        self.scopes.push_scope(Some(func_decl.id));
         for param in &func_decl.parameters {
             self.scopes.local.last_mut().unwrap().add_local(*param);
         }
 
         self.visit_body(ast, &func_decl.body)?;
+        let result = self.expect_return(ast, func_decl);
         self.scopes.pop_scope();
 
-        Ok(())
+        result
+    }
+
+    fn visit_struct_decl(&mut self, _ast: &mut Ast, struct_decl: &StructDeclaration) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_struct_decl");
+
+        self.expect_non_recursive(&struct_decl.identifier)
+    }
+
+    fn visit_enum_decl(&mut self, _ast: &mut Ast, enum_decl: &EnumDeclaration) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_enum_decl");
+
+        self.expect_non_recursive(&enum_decl.identifier)
     }
 
     fn visit_return_statement(
@@ -123,19 +682,69 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
     fn visit_while_statement(&mut self, ast: &mut Ast, while_statement: &WhileStmt) -> Result<()> {
         log::debug!("TypeAnalyzer::visit_while_statement");
 
-        self.visit_expression(ast, while_statement.condition)?;
+        // The condition runs again before every iteration, so a `break` or `continue` in it
+        // has no clear loop to leave. Like in Rust, they are not allowed there.
+        let loop_depth = std::mem::take(&mut self.loop_depth);
+        let condition = self.visit_expression(ast, while_statement.condition);
+        self.loop_depth = loop_depth;
+        condition?;
         let condition = ast.query_expr(while_statement.condition);
-        expect_type(
+        let condition = expect_type(
             &condition.ty,
             &Type::Bool,
             &condition.span(ast),
             &self.content,
-        )?;
-        self.visit_body(ast, &while_statement.body)?;
+        );
+        self.report(condition);
+        self.visit_loop_body(ast, &while_statement.body)?;
 
         Ok(())
     }
 
+    fn visit_for_statement(
+        &mut self,
+        ast: &mut Ast,
+        for_statement: &ForStmt,
+        stmt: &Stmt,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_for_statement");
+
+        let bounds = [
+            Some(for_statement.start),
+            Some(for_statement.end),
+            for_statement.step,
+        ];
+        for bound in bounds.into_iter().flatten() {
+            self.visit_expression(ast, bound)?;
+            let bound = ast.query_expr(bound);
+            let bound = expect_type(&bound.ty, &Type::Int, &bound.span(ast), &self.content);
+            self.report(bound);
+        }
+
+        self.scopes.push_scope(None);
+        let var = self
+            .scopes
+            .new_var(for_statement.identifier.span.literal.clone(), Type::Int);
+        ast.set_var_stmt(&stmt.id, var);
+
+        let result = self.visit_loop_body(ast, &for_statement.body);
+        self.scopes.pop_scope();
+
+        result
+    }
+
+    fn visit_break_statement(&mut self, _ast: &mut Ast, break_statement: &BreakStmt) -> Result<()> {
+        self.expect_in_loop(&break_statement.break_keyword)
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        _ast: &mut Ast,
+        continue_statement: &ContinueStmt,
+    ) -> Result<()> {
+        self.expect_in_loop(&continue_statement.continue_keyword)
+    }
+
     fn visit_block_expr(
         &mut self,
         ast: &mut Ast,
@@ -160,23 +769,29 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
     fn visit_if_expression(&mut self, ast: &mut Ast, if_expr: &IfExpr, expr: &Expr) -> Result<()> {
         log::debug!("TypeAnalyzer::visit_if_expression");
 
-        self.scopes.push_scope(None);
         self.visit_expression(ast, if_expr.condition)?;
 
         let condition = ast.query_expr(if_expr.condition);
-        expect_type(
+        let condition = expect_type(
             &condition.ty,
             &Type::Bool,
             &condition.span(ast),
             &self.content,
-        )?;
+        );
+        self.report(condition);
 
-        self.visit_body(ast, &if_expr.then_branch)?;
-        let mut type_ = Type::Void;
+        // Each branch has its own scope, a variable declared in one is not visible in the other.
+        self.scopes.push_scope(None);
+        let then_branch = self.visit_body(ast, &if_expr.then_branch);
+        self.scopes.pop_scope();
+        then_branch?;
+
+        let mut type_ = Ok(Type::Void);
         if let Some(else_branch) = &if_expr.else_branch {
             self.scopes.push_scope(None);
-            self.visit_body(ast, &else_branch.body)?;
+            let body = self.visit_body(ast, &else_branch.body);
             self.scopes.pop_scope();
+            body?;
 
             let then_type = if_expr.then_branch.ty(ast).unwrap_or(Type::Void);
             let else_type = else_branch.body.ty(ast).unwrap_or(Type::Void);
@@ -191,11 +806,10 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
                 &else_type,
                 &if_expr.if_keyword.span,
                 &self.content,
-            )?;
-            self.scopes.pop_scope();
+            );
         }
 
-        ast.update_type(expr.id, type_);
+        ast.update_type(expr.id, type_?);
 
         Ok(())
     }
@@ -211,13 +825,30 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
         self.visit_expression(ast, let_statement.initializer)?;
         let init = ast.query_expr(let_statement.initializer).clone();
 
+        // The variable is declared even when this fails, so its uses are still checked.
         let typ = if let Some(ann) = &let_statement.type_annotation {
-            let typ = parse_type(&ann.type_name, &self.content)?;
-            let init_type = init.clone().ty;
-
-            expect_type(&init_type, &typ, &ann.type_name.clone().span, &self.content)?;
-
-            typ
+            match parse_type(&ann.type_name, self.scopes.global, &self.content) {
+                Ok(typ) => {
+                    let init_type = init.clone().ty;
+                    let init_type =
+                        expect_type(&init_type, &typ, &ann.type_name.clone().span, &self.content);
+                    self.report(init_type);
+
+                    typ
+                }
+                Err(error) => {
+                    self.diagnostics.report(error);
+                    Type::Error
+                }
+            }
+        } else if matches!(init.ty, Type::Array(_)) && init.ty.is_unresolved() {
+            // Only an empty array literal leaves a type to infer, like `[]` or `[[]]`.
+            let error = CannotInferType(init.span(ast), self.content.clone());
+            self.diagnostics.report(error);
+            Type::Error
+        } else if init.ty.is_unresolved() {
+            // The initializer failed to resolve, which was reported already.
+            Type::Error
         } else {
             init.ty.clone()
         };
@@ -244,6 +875,14 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
         log::debug!("TypeAnalyzer::visit_call_expression func: {:?}", func);
 
         if let Some(func) = func {
+            match self.scopes.current_function_id() {
+                Some(caller) => self.calls.entry(caller).or_default().push(func),
+                None => self.top_level_calls.push((
+                    func,
+                    call_expression.callee.span.clone(),
+                    self.scopes.global.global_variables.len(),
+                )),
+            }
             let func = self.scopes.global.functions.get(&func).unwrap();
             let actual_args = call_expression.arguments.len();
             let expected_args = func.parameters.len();
@@ -267,12 +906,13 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
                 let argument_expression = ast.query_expr(*argument);
                 let param = self.scopes.global.variables.get(param);
 
-                expect_type(
+                let argument = expect_type(
                     &argument_expression.ty,
                     &param.unwrap().type_,
                     &argument_expression.span(ast),
                     &self.content,
-                )?;
+                );
+                self.report(argument);
             }
 
             ast.update_type(expr.id, return_type);
@@ -280,32 +920,84 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
         } else if let Some(scope) = call_expression.scope {
             let scope = ast.query_expr(scope);
 
-            let expr = match &scope.kind {
-                ExprKind::ScopedIdentifier { path } => {
-                    if path.first().unwrap().span.literal == "std" {
-                        let module = STD_MODULES
-                            .get(&path[1].span.literal[..])
-                            .expect("Module not found");
-                        let function = module.get(&call_expression.callee.span.literal[..]);
-
-                        if let Some(function) = function {
-                            let return_type = function.clone();
-                            for argument in &call_expression.arguments {
-                                self.visit_expression(ast, *argument)?;
+            let expr =
+                match &scope.kind {
+                    ExprKind::ScopedIdentifier { path } => {
+                        if path.first().unwrap().span.literal == "std" {
+                            // `std::println()` names no module.
+                            let module = path.get(1).map(|token| token.span.literal.as_str());
+                            let function =
+                                module.and_then(|module| STD_MODULES.get(module)).and_then(
+                                    |module| module.get(&call_expression.callee.span.literal[..]),
+                                );
+
+                            if let Some(function) = function {
+                                let is_array = module == Some("array");
+                                for argument in &call_expression.arguments {
+                                    self.visit_expression(ast, *argument)?;
+                                }
+                                let return_type = if is_array {
+                                    self.array_call_type(ast, call_expression)?
+                                } else {
+                                    self.expect_std_arguments(ast, call_expression, function)?;
+                                    function.return_type.clone()
+                                };
+
+                                ast.update_type(expr.id, return_type);
+                            } else {
+                                let name = match module {
+                                    Some(module) => {
+                                        format!("{}::{}", module, call_expression.function_name())
+                                    }
+                                    None => call_expression.function_name().to_string(),
+                                };
+                                let candidates = std_functions().map(|(std_module, function)| {
+                                    let path = format!("{}::{}", std_module, function);
+                                    let compared = match module {
+                                        Some(_) => path.clone(),
+                                        None => function.to_string(),
+                                    };
+                                    (compared, format!("std::{}", path))
+                                });
+                                let suggestion = closest_match(&name, candidates);
+
+                                return Err(CallToUndeclaredFunction(
+                                    format!("std::{}", name),
+                                    suggestion,
+                                    call_expression.callee.span.clone(),
+                                    self.content.clone(),
+                                ));
                             }
-
-                            ast.update_type(expr.id, return_type);
                         } else {
-                            return Err(CallToUndeclaredFunction(
-                                call_expression.callee.span.literal.clone(),
-                                call_expression.callee.span.clone(),
+                            // Only the bundled std has modules, `io::println()` is the likely mistake.
+                            let segments: Vec<&str> = path
+                                .iter()
+                                .map(|token| token.span.literal.as_str())
+                                .collect();
+                            let name = format!(
+                                "{}::{}",
+                                segments.join("::"),
+                                call_expression.function_name()
+                            );
+                            let candidates = std_functions().map(|(module, function)| {
+                                let path = format!("{}::{}", module, function);
+                                (path.clone(), format!("std::{}", path))
+                            });
+                            let suggestion = closest_match(&name, candidates);
+
+                            return Err(NotFound(
+                                name,
+                                suggestion,
+                                TextSpan::combine(vec![
+                                    path[0].span.clone(),
+                                    call_expression.callee.span.clone(),
+                                ]),
                                 self.content.clone(),
                             ));
                         }
                     }
-                }
-                _ => unreachable!("Invalid scope"),
-            };
+                    _ => unreachable!("Invalid scope"),
+                };
 
             Ok(())
         } else if STD_RESERVED_WORDS.contains(&&call_expression.callee.span.literal[..]) {
@@ -326,8 +1018,21 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
         } else {
             log::debug!("TypeAnalyzer::visit_call_expression Call to undeclared function");
 
+            let functions = self.scopes.global.functions.values();
+            let candidates = functions
+                .map(|function| (function.name.as_str(), function.name.clone()))
+                .chain(
+                    STD_RESERVED_WORDS
+                        .iter()
+                        .map(|word| (*word, word.to_string())),
+                )
+                .chain(std_functions().map(|(module, function)| {
+                    (function, format!("std::{}::{}", module, function))
+                }));
+
             Err(CallToUndeclaredFunction(
                 call_expression.callee.span.literal.clone(),
+                closest_match(call_expression.function_name(), candidates),
                 call_expression.callee.span.clone(),
                 self.content.clone(),
             ))
@@ -349,14 +1054,17 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
 
         if let Some(var) = var {
             ast.set_variable(expr.id, var);
+            self.record_global_use(var);
             let var = self.scopes.global.variables.get(&var).unwrap();
-            let expr = ast.query_expr(assignment_expression.expression).clone();
+            let value = ast.query_expr(assignment_expression.expression).clone();
 
-            expect_type(&expr.ty, &var.type_, &expr.span(ast), &self.content)?;
+            expect_type(&value.ty, &var.type_, &value.span(ast), &self.content)?;
             ast.update_type(expr.id, var.type_.clone());
         } else {
+            let suggestion = self.suggest_variable(&ident);
             return Err(NotFound(
                 ident,
+                suggestion,
                 assignment_expression.identifier.span.clone(),
                 self.content.clone(),
             ));
@@ -381,9 +1089,16 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
                     let var = self.scopes.global.variables.get(&id).unwrap();
                     ast.update_type(expr.id, var.type_.clone());
                     ast.set_variable(expr.id, id);
+                    self.record_global_use(id);
                 }
                 None => {
-                    return Err(NotFound(name, expr.span(ast), self.content.clone()));
+                    let suggestion = self.suggest_variable(&name);
+                    return Err(NotFound(
+                        name,
+                        suggestion,
+                        expr.span(ast),
+                        self.content.clone(),
+                    ));
                 }
             }
         }
@@ -404,6 +1119,37 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
         Ok(())
     }
 
+    fn visit_float_expression(&mut self, ast: &mut Ast, _: &FloatExpr, expr: &Expr) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_float_expression");
+
+        ast.update_type(expr.id, Type::Float);
+
+        Ok(())
+    }
+
+    fn visit_cast_expression(&mut self, ast: &mut Ast, cast: &CastExpr, expr: &Expr) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_cast_expression");
+
+        self.visit_expression(ast, cast.expression)?;
+        let value = ast.query_expr(cast.expression);
+        let target = parse_type(&cast.type_name, self.scopes.global, &self.content)?;
+
+        // Only numbers convert, anything else has no obvious meaning.
+        let valid = matches!(value.ty, Type::Error) || value.ty.is_numeric() && target.is_numeric();
+        if !valid {
+            return Err(InvalidCast(
+                value.ty.to_str(),
+                target.to_str(),
+                expr.span(ast),
+                self.content.clone(),
+            ));
+        }
+
+        ast.update_type(expr.id, target);
+
+        Ok(())
+    }
+
     fn visit_string_expression(
         &mut self,
         ast: &mut Ast,
@@ -441,17 +1187,342 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
         self.visit_expression(ast, unary_expression.operand)?;
         let op = ast.query_expr(unary_expression.operand).clone();
         let type_ = match unary_expression.operator.kind {
+            UnOpKind::Minus if matches!(op.ty, Type::Float) => (Type::Float, Type::Float),
             UnOpKind::Minus => (Type::Int, Type::Int),
             UnOpKind::BitwiseNot => (Type::Int, Type::Int),
+            UnOpKind::LogicalNot => (Type::Bool, Type::Bool),
         };
 
-        expect_type(&op.ty, &type_.0, &op.span(ast), &self.content)?;
+        let operand = expect_type(&op.ty, &type_.0, &op.span(ast), &self.content);
+        self.report(operand);
 
         ast.update_type(expr.id, type_.1);
 
         Ok(())
     }
 
+    fn visit_parenthesized_expression(
+        &mut self,
+        ast: &mut Ast,
+        parenthesized_expression: &ParenthesizedExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_parenthesized_expression");
+
+        self.visit_expression(ast, parenthesized_expression.inner)?;
+        let inner = ast.query_expr(parenthesized_expression.inner).ty.clone();
+        ast.update_type(expr.id, inner);
+
+        Ok(())
+    }
+
+    fn visit_struct_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        struct_literal: &StructLiteralExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_struct_literal_expression");
+
+        let name = struct_literal.struct_name().to_string();
+        let structure = self
+            .scopes
+            .global
+            .get_struct(&name)
+            .cloned()
+            .expect("Struct literal was parsed for an undeclared struct");
+
+        let mut initialized: Vec<&str> = vec![];
+        for initializer in &struct_literal.fields {
+            let field_name = &initializer.identifier.span.literal;
+            let field = match structure.field(field_name) {
+                Some(field) => field,
+                None => {
+                    return Err(UnknownField(
+                        field_name.clone(),
+                        name,
+                        initializer.identifier.span.clone(),
+                        self.content.clone(),
+                    ))
+                }
+            };
+            initialized.push(field_name);
+
+            self.visit_expression(ast, initializer.value)?;
+            let value = ast.query_expr(initializer.value);
+            let value = expect_type(&value.ty, &field.type_, &value.span(ast), &self.content);
+            self.report(value);
+        }
+
+        let missing: Vec<&str> = structure
+            .fields
+            .iter()
+            .map(|field| &field.name[..])
+            .filter(|field| !initialized.contains(field))
+            .collect();
+        if !missing.is_empty() {
+            return Err(MissingFields(
+                name,
+                missing.join(", "),
+                expr.span(ast),
+                self.content.clone(),
+            ));
+        }
+
+        ast.update_type(expr.id, Type::Struct(name));
+
+        Ok(())
+    }
+
+    fn visit_array_literal_expression(
+        &mut self,
+        ast: &mut Ast,
+        array_literal: &ArrayLiteralExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_array_literal_expression");
+
+        // The first element decides the element type, `[]` stays unresolved until it meets
+        // an annotation or a parameter.
+        let mut element = Type::Unresolved;
+        for (index, element_id) in array_literal.elements.iter().enumerate() {
+            self.visit_expression(ast, *element_id)?;
+            let value = ast.query_expr(*element_id);
+            if index == 0 {
+                if matches!(value.ty, Type::Void) {
+                    return Err(TypeMismatch(
+                        value.ty.to_str(),
+                        "array element".to_string(),
+                        value.span(ast),
+                        self.content.clone(),
+                    ));
+                }
+                element = value.ty.clone();
+            } else {
+                expect_type(&value.ty, &element, &value.span(ast), &self.content)?;
+            }
+        }
+
+        ast.update_type(expr.id, Type::Array(Box::new(element)));
+
+        Ok(())
+    }
+
+    fn visit_index_expression(
+        &mut self,
+        ast: &mut Ast,
+        index_expression: &IndexExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_index_expression");
+
+        self.visit_expression(ast, index_expression.object)?;
+        self.visit_expression(ast, index_expression.index)?;
+
+        let index = ast.query_expr(index_expression.index);
+        expect_type(&index.ty, &Type::Int, &index.span(ast), &self.content)?;
+
+        let object = ast.query_expr(index_expression.object);
+        let type_ = match &object.ty {
+            Type::Array(element) => *element.clone(),
+            Type::Error => Type::Error,
+            other => {
+                return Err(TypeMismatch(
+                    other.to_str(),
+                    "array".to_string(),
+                    object.span(ast),
+                    self.content.clone(),
+                ))
+            }
+        };
+
+        ast.update_type(expr.id, type_);
+
+        Ok(())
+    }
+
+    fn visit_field_access_expression(
+        &mut self,
+        ast: &mut Ast,
+        field_access: &FieldAccessExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_field_access_expression");
+
+        self.visit_expression(ast, field_access.object)?;
+        let object = ast.query_expr(field_access.object);
+
+        let type_ = match &object.ty {
+            Type::Struct(name) => {
+                let structure = self.scopes.global.get_struct(name).unwrap();
+                match structure.field(field_access.field_name()) {
+                    Some(field) => field.type_.clone(),
+                    None => {
+                        return Err(UnknownField(
+                            field_access.field_name().to_string(),
+                            name.clone(),
+                            field_access.field.span.clone(),
+                            self.content.clone(),
+                        ))
+                    }
+                }
+            }
+            Type::Error => Type::Error,
+            other => {
+                return Err(TypeMismatch(
+                    other.to_str(),
+                    "struct".to_string(),
+                    object.span(ast),
+                    self.content.clone(),
+                ))
+            }
+        };
+
+        ast.update_type(expr.id, type_);
+
+        Ok(())
+    }
+
+    fn visit_place_assignment_expression(
+        &mut self,
+        ast: &mut Ast,
+        place_assignment: &PlaceAssignExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_place_assignment_expression");
+
+        self.expect_place(ast, place_assignment.target)?;
+
+        self.visit_expression(ast, place_assignment.target)?;
+        self.visit_expression(ast, place_assignment.expression)?;
+
+        let target = ast.query_expr(place_assignment.target).ty.clone();
+        let value = ast.query_expr(place_assignment.expression);
+        expect_type(&value.ty, &target, &value.span(ast), &self.content)?;
+        ast.update_type(expr.id, target);
+
+        Ok(())
+    }
+
+    fn visit_enum_variant_expression(
+        &mut self,
+        ast: &mut Ast,
+        enum_variant: &EnumVariantExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_enum_variant_expression");
+
+        let name = enum_variant.enum_name().to_string();
+        let variant = self
+            .scopes
+            .global
+            .get_enum(&name)
+            .and_then(|enumeration| enumeration.variant(enum_variant.variant_name()))
+            .cloned()
+            .ok_or_else(|| {
+                UnknownVariant(
+                    enum_variant.variant_name().to_string(),
+                    name.clone(),
+                    enum_variant.variant.span.clone(),
+                    self.content.clone(),
+                )
+            })?;
+
+        if variant.payload.len() != enum_variant.arguments.len() {
+            return Err(InvalidArguments(
+                variant.payload.len(),
+                enum_variant.arguments.len(),
+                expr.span(ast),
+                self.content.clone(),
+            ));
+        }
+
+        for (argument, type_) in enum_variant.arguments.iter().zip(&variant.payload) {
+            self.visit_expression(ast, *argument)?;
+            let argument = ast.query_expr(*argument);
+            let argument = expect_type(&argument.ty, type_, &argument.span(ast), &self.content);
+            self.report(argument);
+        }
+
+        ast.update_type(expr.id, Type::Enum(name));
+
+        Ok(())
+    }
+
+    /// A path that names a known enum parses as `EnumVariant` and the path of a call is its
+    /// scope, so a path reaching here names nothing, like `Nope::A` or `std::io`.
+    fn visit_scoped_identifier_expression(
+        &mut self,
+        ast: &mut Ast,
+        path: &[Token],
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_scoped_identifier_expression");
+
+        let segments: Vec<&str> = path
+            .iter()
+            .map(|token| token.span.literal.as_str())
+            .collect();
+        let rest = segments[1..].join("::");
+        let enums = self.scopes.global.enums.values();
+        let suggestion = closest_match(
+            segments[0],
+            enums.map(|enumeration| {
+                let suggestion = format!("{}::{}", enumeration.name, rest);
+                (enumeration.name.as_str(), suggestion)
+            }),
+        );
+
+        Err(NotFound(
+            segments.join("::"),
+            suggestion,
+            expr.span(ast),
+            self.content.clone(),
+        ))
+    }
+
+    fn visit_match_expression(
+        &mut self,
+        ast: &mut Ast,
+        match_expr: &MatchExpr,
+        expr: &Expr,
+    ) -> Result<()> {
+        log::debug!("TypeAnalyzer::visit_match_expression");
+
+        self.visit_expression(ast, match_expr.scrutinee)?;
+        let scrutinee = ast.query_expr(match_expr.scrutinee).ty.clone();
+
+        // Like the branches of an `if`, every arm has to produce the same type.
+        let mut type_: Option<Type> = None;
+        for (index, arm) in match_expr.arms.iter().enumerate() {
+            self.scopes.push_scope(None);
+            if let Err(error) = self.visit_pattern(ast, &arm.pattern, &scrutinee, expr.id, index) {
+                self.diagnostics.report(error);
+            }
+            self.visit_expression(ast, arm.body)?;
+            self.scopes.pop_scope();
+
+            let body = ast.query_expr(arm.body);
+            type_ = Some(match type_ {
+                None => body.ty.clone(),
+                Some(expected) => expect_type(&body.ty, &expected, &body.span(ast), &self.content)?,
+            });
+        }
+
+        let missing = self.missing_patterns(ast, match_expr, &scrutinee);
+        if !missing.is_empty() {
+            return Err(NonExhaustiveMatch(
+                missing.join(", "),
+                match_expr.match_keyword.span.clone(),
+                self.content.clone(),
+            ));
+        }
+
+        ast.update_type(expr.id, type_.unwrap_or(Type::Void));
+
+        Ok(())
+    }
+
     fn visit_binary_expression(
         &mut self,
         ast: &mut Ast,
@@ -466,27 +1537,37 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
         let left = ast.query_expr(binary_expression.left).clone();
         let right = ast.query_expr(binary_expression.right).clone();
 
+        // Arithmetic and comparisons work on two ints or two floats, mixing them needs `as`.
+        let number = match (&left.ty, &right.ty) {
+            (Type::Float, _) | (Type::Error, Type::Float) => Type::Float,
+            _ => Type::Int,
+        };
+
         let operator = &binary_expression.operator.kind;
         let result: (Type, Type, Type) = match operator {
-            BinOpKind::Plus => (Type::Int, Type::Int, Type::Int),
-            BinOpKind::Minus => (Type::Int, Type::Int, Type::Int),
-            BinOpKind::Multiply => (Type::Int, Type::Int, Type::Int),
-            BinOpKind::Divide => (Type::Int, Type::Int, Type::Int),
+            BinOpKind::Plus => (number.clone(), number.clone(), number),
+            BinOpKind::Minus => (number.clone(), number.clone(), number),
+            BinOpKind::Multiply => (number.clone(), number.clone(), number),
+            BinOpKind::Divide => (number.clone(), number.clone(), number),
             BinOpKind::Power => (Type::Int, Type::Int, Type::Int),
             BinOpKind::BitwiseAnd => (Type::Int, Type::Int, Type::Int),
             BinOpKind::BitwiseOr => (Type::Int, Type::Int, Type::Int),
             BinOpKind::BitwiseXor => (Type::Int, Type::Int, Type::Int),
-            BinOpKind::Equals => (Type::Int, Type::Int, Type::Bool),
-            BinOpKind::NotEquals => (Type::Int, Type::Int, Type::Bool),
-            BinOpKind::LessThan => (Type::Int, Type::Int, Type::Bool),
-            BinOpKind::LessThanOrEqual => (Type::Int, Type::Int, Type::Bool),
-            BinOpKind::GreaterThan => (Type::Int, Type::Int, Type::Bool),
-            BinOpKind::GreaterThanOrEqual => (Type::Int, Type::Int, Type::Bool),
+            BinOpKind::Equals => (number.clone(), number, Type::Bool),
+            BinOpKind::NotEquals => (number.clone(), number, Type::Bool),
+            BinOpKind::LessThan => (number.clone(), number, Type::Bool),
+            BinOpKind::LessThanOrEqual => (number.clone(), number, Type::Bool),
+            BinOpKind::GreaterThan => (number.clone(), number, Type::Bool),
+            BinOpKind::GreaterThanOrEqual => (number.clone(), number, Type::Bool),
             BinOpKind::Modulo => (Type::Int, Type::Int, Type::Int),
+            BinOpKind::LogicalAnd => (Type::Bool, Type::Bool, Type::Bool),
+            BinOpKind::LogicalOr => (Type::Bool, Type::Bool, Type::Bool),
         };
 
-        expect_type(&left.ty, &result.0, &left.span(ast), &self.content)?;
-        expect_type(&right.ty, &result.1, &right.span(ast), &self.content)?;
+        let left = expect_type(&left.ty, &result.0, &left.span(ast), &self.content);
+        self.report(left);
+        let right = expect_type(&right.ty, &result.1, &right.span(ast), &self.content);
+        self.report(right);
 
         ast.update_type(expr.id, result.2);
 
@@ -494,6 +1575,31 @@ impl<'a> ASTWalker for TypeAnalyzer<'a> {
     }
 }
 
+/// Returns true if running `stmts` always ends in a `return`.
+fn always_returns(ast: &Ast, stmts: &[ID]) -> bool {
+    stmts.iter().any(|stmt| match &ast.query_stmt(*stmt).kind {
+        StmtKind::Return(_) => true,
+        StmtKind::Expr(expr) => expr_always_returns(ast, *expr),
+        _ => false,
+    })
+}
+
+fn expr_always_returns(ast: &Ast, expr: ID) -> bool {
+    match &ast.query_expr(expr).kind {
+        ExprKind::If(if_expr) => if_expr.else_branch.as_ref().is_some_and(|else_branch| {
+            always_returns(ast, &if_expr.then_branch.stmts)
+                && always_returns(ast, &else_branch.body.stmts)
+        }),
+        ExprKind::Block(block) => always_returns(ast, &block.stmts),
+        ExprKind::Match(match_expr) => match_expr
+            .arms
+            .iter()
+            .all(|arm| expr_always_returns(ast, arm.body)),
+        ExprKind::Parenthesized(parenthesized) => expr_always_returns(ast, parenthesized.inner),
+        _ => false,
+    }
+}
+
 pub fn expect_type(type1: &Type, type2: &Type, span: &TextSpan, content: &String) -> Result<Type> {
     if !Type::is_assignable_to(type1, type2) {
         return Err(TypeMismatch(
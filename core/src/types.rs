@@ -1,5 +1,6 @@
 use crate::ast::expr::ExprKind;
 use crate::error::error::Error::InvalidType;
+use crate::global_context::GlobalContext;
 use crate::lexer::token::Token;
 use crate::Result;
 use std::fmt::{Display, Formatter};
@@ -7,22 +8,29 @@ use std::fmt::{Display, Formatter};
 #[derive(Debug, Clone)]
 pub enum Type {
     Int,
+    Float,
     Bool,
     Void,
     Unresolved,
     Error,
     String,
+    Struct(String),
+    Enum(String),
+    Array(Box<Type>),
 }
 
 impl Display for Type {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let type_name = match self {
             Type::Int => "int",
+            Type::Float => "float",
             Type::Bool => "bool",
             Type::Unresolved => "unresolved",
             Type::Void => "void",
             Type::Error => "?",
             Type::String => "string",
+            Type::Struct(name) | Type::Enum(name) => name,
+            Type::Array(element) => return write!(f, "[{}]", element),
         };
 
         write!(f, "{}", type_name)
@@ -31,19 +39,29 @@ impl Display for Type {
 
 impl Type {
     pub fn is_assignable_to(&self, other: &Type) -> bool {
-        matches!(
-            (self, other),
-            (Type::Int, Type::Int) | (Type::Void, Type::Void)
-                | (Type::Bool, Type::Bool)
-                | (Type::Error, _)
-                | (_, Type::Error)
-                | (Type::String, Type::String)
-        )
+        match (self, other) {
+            (Type::Struct(a), Type::Struct(b)) | (Type::Enum(a), Type::Enum(b)) => a == b,
+            // The element type of an empty literal is taken from where it is used.
+            (Type::Array(a), Type::Array(b)) => {
+                matches!(**a, Type::Unresolved) || a.is_assignable_to(b)
+            }
+            _ => matches!(
+                (self, other),
+                (Type::Int, Type::Int)
+                    | (Type::Float, Type::Float)
+                    | (Type::Void, Type::Void)
+                    | (Type::Bool, Type::Bool)
+                    | (Type::Error, _)
+                    | (_, Type::Error)
+                    | (Type::String, Type::String)
+            ),
+        }
     }
 
     pub fn from_str(s: &str) -> Option<Type> {
         match s {
             "int" => Some(Type::Int),
+            "float" => Some(Type::Float),
             "bool" => Some(Type::Bool),
             "void" => Some(Type::Void),
             "string" => Some(Type::String),
@@ -54,19 +72,33 @@ impl Type {
     pub fn to_str(&self) -> String {
         match self {
             Type::Int => "int".to_string(),
+            Type::Float => "float".to_string(),
             Type::Bool => "bool".to_string(),
             Type::Void => "void".to_string(),
             Type::String => "string".to_string(),
             Type::Unresolved => "unresolved".to_string(),
             Type::Error => "?".to_string(),
+            Type::Struct(name) | Type::Enum(name) => name.clone(),
+            Type::Array(element) => format!("[{}]", element.to_str()),
         }
     }
-}
 
-pub fn parse_type(s: &Token, content: &String) -> Result<Type> {
-    let name = Type::from_str(&s.span.literal);
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Float)
+    }
+
+    /// Returns true if the type is not fully known, like the type of `[]`.
+    pub fn is_unresolved(&self) -> bool {
+        match self {
+            Type::Unresolved => true,
+            Type::Array(element) => element.is_unresolved(),
+            _ => false,
+        }
+    }
+}
 
-    match name {
+pub fn parse_type(s: &Token, ctx: &GlobalContext, content: &String) -> Result<Type> {
+    match resolve_type_name(&s.span.literal, ctx) {
         Some(t) => Ok(t),
         None => Err(InvalidType(
             s.span.literal.clone(),
@@ -75,3 +107,19 @@ pub fn parse_type(s: &Token, content: &String) -> Result<Type> {
         )),
     }
 }
+
+fn resolve_type_name(name: &str, ctx: &GlobalContext) -> Option<Type> {
+    if let Some(element) = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+        return match resolve_type_name(element, ctx)? {
+            Type::Void => None,
+            element => Some(Type::Array(Box::new(element))),
+        };
+    }
+
+    match Type::from_str(name) {
+        Some(t) => Some(t),
+        None if ctx.lookup_struct(name).is_some() => Some(Type::Struct(name.to_string())),
+        None if ctx.lookup_enum(name).is_some() => Some(Type::Enum(name.to_string())),
+        None => None,
+    }
+}
@@ -0,0 +1,771 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use pulse_core::build::BuildProcess;
+
+/// Compiles `source` to C++, builds it with `g++` and returns what the program printed.
+/// Returns `None` when no C++ compiler is installed, so the tests don't fail on such machines.
+fn run_compiled(name: &str, source: &str) -> Option<String> {
+    let output = run_program(name, source)?;
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Like `run_compiled`, but returns the exit status and both outputs of the program.
+fn run_program(name: &str, source: &str) -> Option<Output> {
+    let mut build = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"));
+    build.analyze().expect("Source should type check");
+    let cpp = build.compile().expect("Source should compile");
+
+    let build = std::env::temp_dir().join(format!("pulse-codegen-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&build);
+    fs::create_dir_all(build.join("source")).unwrap();
+    fs::create_dir_all(build.join("std")).unwrap();
+    let lib = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../cli/lib");
+    for entry in fs::read_dir(lib).unwrap() {
+        let entry = entry.unwrap();
+        fs::copy(entry.path(), build.join("std").join(entry.file_name())).unwrap();
+    }
+    fs::write(build.join("source/main.cpp"), cpp).unwrap();
+
+    let compiled = Command::new("g++")
+        .arg("source/main.cpp")
+        .arg("-fwrapv")
+        .arg("-o")
+        .arg("main")
+        .current_dir(&build)
+        .output()
+        .ok()?;
+    assert!(
+        compiled.status.success(),
+        "g++ failed:\n{}",
+        String::from_utf8_lossy(&compiled.stderr)
+    );
+
+    let output = Command::new(build.join("main")).output().unwrap();
+    let _ = fs::remove_dir_all(&build);
+    Some(output)
+}
+
+fn lines(output: &str) -> Vec<&str> {
+    output.lines().collect()
+}
+
+#[test]
+fn integers_wrap_around_at_32_bits() {
+    let source = r#"
+fn main() -> int {
+    let big = 2147483647;
+    println(big + 1, 65536 * 65536);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("wrap", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["-2147483648 0"]);
+}
+
+#[test]
+fn print_separates_arguments_with_one_space() {
+    let source = r#"
+fn main() -> int {
+    print(0);
+    print(3);
+    print(9);
+    println();
+    println(true, false, "s");
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("print", source) else {
+        return;
+    };
+
+    assert_eq!(output, "039\ntrue false s\n");
+}
+
+#[test]
+fn integer_division_by_zero_is_a_runtime_error() {
+    let source = r#"
+fn main() -> int {
+    let zero = 0;
+    let two = 0 - 2;
+    let one = 0 - 1;
+    let min = 0 - 2147483647 - 1;
+    println(7 / two, 0 - 7 % 3, min / one, min % one);
+    println(1 % zero);
+    return 0;
+}
+"#;
+    let Some(output) = run_program("divide", source) else {
+        return;
+    };
+
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "-3 -1 -2147483648 0\n"
+    );
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Runtime error: Attempted to divide by zero at main.pulse:8:15"));
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    let source = r#"
+fn check(x: bool) -> bool {
+    println("check", x);
+    return x;
+}
+fn main() -> int {
+    let a = false && check(true);
+    let b = true || check(false);
+    let c = !a && check(b) || check(false);
+    println(a, b, c, !c);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("logical", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["check true", "false true true false"]);
+}
+
+#[test]
+fn for_loops_evaluate_their_step_once() {
+    let source = r#"
+fn main() -> int {
+    let step = 2;
+    for i in 0..7 step step {
+        step = 100;
+        println(i);
+    }
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("step", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["0", "2", "4", "6"]);
+}
+
+#[test]
+fn ranges_ending_at_the_largest_int_stop() {
+    let source = r#"
+fn main() -> int {
+    let count = 0;
+    for i in 2147483640..=2147483647 {
+        count = count + 1;
+    }
+    for i in 2147483600..2147483647 step 40 {
+        println(i);
+    }
+    println(count);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("largest", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["2147483600", "2147483640", "8"]);
+}
+
+#[test]
+fn for_loops_break_and_continue() {
+    let source = r#"
+fn main() -> int {
+    for i in 0..10 step 3 {
+        if i == 3 {
+            continue;
+        }
+        if i > 7 {
+            break;
+        }
+        println(i);
+    }
+    for i in 1..=3 {
+        print(i);
+    }
+    println();
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("for", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["0", "6", "123"]);
+}
+
+#[test]
+fn steps_that_are_not_positive_are_runtime_errors() {
+    let source = r#"
+fn main() -> int {
+    let step = 0;
+    for i in 0..3 step step {}
+    return 0;
+}
+"#;
+    let Some(output) = run_program("step-zero", source) else {
+        return;
+    };
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Runtime error: Step of a for loop must be positive, got 0 at main.pulse:4:24"));
+}
+
+#[test]
+fn structs_are_built_read_and_assigned_by_field() {
+    let source = r#"
+struct Point { x: int, y: int }
+struct Line { start: Point, end: Point }
+fn shift(p: Point, by: int) -> Point {
+    return Point { y: p.y + by, x: p.x + by };
+}
+fn main() -> int {
+    let line = Line { start: Point { x: 1, y: 2 }, end: Point { x: 3, y: 4 } };
+    line.end.x = 10;
+    let moved = shift(line.start, 5);
+    println(line.end.x + line.start.y, moved);
+    println(line);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("structs", source) else {
+        return;
+    };
+
+    assert_eq!(
+        lines(&output),
+        vec![
+            "12 Point { x: 6, y: 7 }",
+            "Line { start: Point { x: 1, y: 2 }, end: Point { x: 10, y: 4 } }"
+        ]
+    );
+}
+
+#[test]
+fn enums_carry_payloads_into_match_arms() {
+    let source = r#"
+enum Shape { Circle(int), Rect(int, int), Empty }
+fn area(shape: Shape) -> int {
+    return match shape {
+        Shape::Circle(r) => 3 * r * r,
+        Shape::Rect(w, h) => { let area = w * h; area },
+        _ => 0,
+    };
+}
+fn main() -> int {
+    let circle = Shape::Circle(2);
+    let rect = Shape::Rect(2, 5);
+    match rect {
+        Shape::Rect(w, _) => println("rect", w),
+        _ => println("other"),
+    }
+    println(area(circle), area(rect), area(Shape::Empty));
+    println(circle, rect, Shape::Empty);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("enums", source) else {
+        return;
+    };
+
+    assert_eq!(
+        lines(&output),
+        vec!["rect 2", "12 10 0", "Circle(2) Rect(2, 5) Empty"]
+    );
+}
+
+#[test]
+fn arrays_grow_and_are_assigned_through_indices() {
+    let source = r#"
+struct P { x: int }
+fn main() -> int {
+    let a = [1, 2];
+    std::array::push(a, 3);
+    let last = std::array::pop(a);
+    std::array::push(a, last * 10);
+    a[0] = a[1] + 5;
+    let grid = [[1], [2, 3]];
+    grid[1][0] = 9;
+    std::array::push(grid[0], 4);
+    let ps = [P { x: 1 }];
+    ps[0].x = 8;
+    println(a, std::array::len(a), grid, ps[0].x);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("arrays", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["[7, 2, 30] 3 [[1, 4], [9, 3]] 8"]);
+}
+
+#[test]
+fn indices_out_of_bounds_are_runtime_errors() {
+    let source = r#"
+fn main() -> int {
+    let a = [1, 2];
+    let i = 2;
+    println(a[i]);
+    return 0;
+}
+"#;
+    let Some(output) = run_program("bounds", source) else {
+        return;
+    };
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        "Runtime error: Index out of bounds: the length is 2 but the index is 2 at main.pulse:5:13"
+    ));
+}
+
+#[test]
+fn floats_print_like_the_interpreter() {
+    let source = r#"
+fn main() -> int {
+    let x = 7.5;
+    let n = 2;
+    let negative = 0.0 - x;
+    println(x / 2.0, 1e3, 2.5e-5, 1234567.0, 1.0 / 3.0);
+    println(n as float / 4.0, x as int, negative as int);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("floats", source) else {
+        return;
+    };
+
+    assert_eq!(
+        lines(&output),
+        vec!["3.75 1000 2.5e-05 1.23457e+06 0.333333", "0.5 7 -7"]
+    );
+}
+
+#[test]
+fn string_escapes_survive_the_generated_cpp() {
+    let source = r#"
+fn main() -> int {
+    println("say \"hi\"\tC:\\path ?? \u{e9}");
+    println("two\nlines");
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("strings", source) else {
+        return;
+    };
+
+    assert_eq!(
+        lines(&output),
+        vec!["say \"hi\"\tC:\\path ?? é", "two", "lines"]
+    );
+}
+
+#[test]
+fn unary_operators_keep_their_operand_grouped() {
+    let source = r#"
+fn main() -> int {
+    let a = 2;
+    let b = 3;
+    let x = 1.5;
+    println(-(a + b), - -a, ~a, ~-a, -x, !(a > b));
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("unary", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["-5 2 -3 1 -1.5 true"]);
+}
+
+#[test]
+fn generated_cpp_keeps_the_pulse_grouping() {
+    let source = r#"
+fn main() -> int {
+    let a = 6;
+    let b = 3;
+    let c = 2;
+    println(a - (b - c), (a + b) * c, a / (b / c), a - b - c);
+    println(a & b == 2, (a | b) ^ c, a & b + 1, a % b * c);
+    println(a > b && b > c, true || false && false, (true || false) && false);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("precedence", source) else {
+        return;
+    };
+
+    assert_eq!(
+        lines(&output),
+        vec!["5 18 6 1", "true 5 4 0", "true true false"]
+    );
+}
+
+#[test]
+fn folded_powers_match_computed_ones() {
+    let source = r#"
+fn main() -> int {
+    let e = 21;
+    println(3 ** 21, 3 ** e, (-2) ** 31, 2 ** -1);
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("power", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["1870418611 1870418611 -2147483648 0"]);
+}
+
+#[test]
+fn powers_wrap_around_at_32_bits() {
+    let source = r#"
+fn main() -> int {
+    let two = 2;
+    let ten = 10;
+    println(two ** ten, two ** 32, -two ** 2, two ** ten % 1000, (0 - 1) ** ten);
+    for i in 0..10 step 3 ** 1 {
+        print(i, "");
+    }
+    println();
+    return 0;
+}
+"#;
+    let Some(output) = run_compiled("power_operator", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["1024 0 4 24 1", "0 3 6 9 "]);
+}
+
+#[test]
+fn trailing_expressions_are_returned() {
+    let source = r#"
+fn sign(n: int) -> int {
+    if n < 0 { 0 - 1 } else { if n == 0 { 0 } else { 1 } }
+}
+fn doubled(n: int) -> int {
+    let m = n;
+    {
+        let twice = m + m;
+        twice
+    }
+}
+fn main() -> int {
+    println(sign(0 - 5), sign(0), sign(5), doubled(21));
+    0
+}
+"#;
+    let Some(output) = run_compiled("trailing", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["-1 0 1 42"]);
+}
+
+#[test]
+fn return_inside_value_if_leaves_the_function() {
+    let source = r#"
+fn k(n: int) -> int {
+    let r = 1 + if n > 0 { if n == 7 { return 70; } 2 } else { 3 };
+    r
+}
+fn main() -> int {
+    println(k(7));
+    println(k(1));
+    println(k(0));
+    0
+}
+"#;
+    let Some(output) = run_compiled("return", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["70", "3", "4"]);
+}
+
+#[test]
+fn continue_inside_value_if_skips_the_iteration() {
+    let source = r#"
+fn main() -> int {
+    for i in 0..4 {
+        println(1 + if i == 1 { if true { continue; } 0 } else { i });
+    }
+    let count = 0;
+    while count < 3 {
+        count = count + 1;
+        println(count + { if count == 2 { continue; } 100 });
+    }
+    0
+}
+"#;
+    let Some(output) = run_compiled("continue", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["1", "3", "4", "101", "103"]);
+}
+
+#[test]
+fn break_inside_value_if_leaves_the_loop() {
+    let source = r#"
+fn main() -> int {
+    for i in 0..5 {
+        let x = 10 * if i == 3 { if true { break; } 0 } else { i };
+        println(x);
+    }
+    println(-1);
+    0
+}
+"#;
+    let Some(output) = run_compiled("break", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["0", "10", "20", "-1"]);
+}
+
+#[test]
+fn return_inside_match_arm_leaves_the_function() {
+    let source = r#"
+enum S { A, B }
+fn f(s: S) -> int {
+    let v = match s { S::A => 1, S::B => { if true { return 99; } 0 } };
+    v + 1
+}
+fn main() -> int {
+    println(f(S::A));
+    println(f(S::B));
+    0
+}
+"#;
+    let Some(output) = run_compiled("match", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["2", "99"]);
+}
+
+#[test]
+fn arguments_are_evaluated_left_to_right() {
+    let source = r#"
+fn bump(x: int) -> int {
+    println("bump", x);
+    x + 1
+}
+fn main() -> int {
+    let a = [1, 2, 3];
+    println(a, std::array::len(a), std::array::pop(a), a);
+    println(bump(1) + bump(2) * bump(3));
+    0
+}
+"#;
+    let Some(output) = run_compiled("order", source) else {
+        return;
+    };
+
+    assert_eq!(
+        lines(&output),
+        vec!["[1, 2, 3] 3 3 [1, 2]", "bump 1", "bump 2", "bump 3", "14"]
+    );
+}
+
+#[test]
+fn value_if_block_and_match_nest_inside_calls_and_operators() {
+    let source = r#"
+enum Size { Small, Large(int) }
+fn add(a: int, b: int) -> int { a + b }
+fn main() -> int {
+    let flag = true;
+    let size = Size::Large(4);
+    println(add(if flag { 1 } else { 2 }, { let t = 3; t * 2 }));
+    println(10 * match size { Size::Small => 0, Size::Large(n) => n } + if !flag { 1 } else { 2 });
+    println(add(add({ 1 }, if flag { if false { 5 } else { 6 } } else { 7 }), 1));
+    0
+}
+"#;
+    let Some(output) = run_compiled("nesting", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["7", "42", "8"]);
+}
+
+#[test]
+fn functions_may_call_functions_defined_after_them() {
+    let source = r#"
+fn main() -> int {
+    println(is_even(10), is_odd(7), is_even(3));
+    0
+}
+fn is_even(n: int) -> bool {
+    if n == 0 { true } else { is_odd(n - 1) }
+}
+fn is_odd(n: int) -> bool {
+    if n == 0 { false } else { is_even(n - 1) }
+}
+"#;
+    let Some(output) = run_compiled("prototypes", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["true true false"]);
+}
+
+#[test]
+fn else_branch_sees_the_variable_the_then_branch_shadows() {
+    let source = r#"
+fn main() -> int {
+    let x = 3;
+    let y = if x > 0 { let x = 5; x } else { x };
+    let w = if x < 0 { let x = 5; x } else { x };
+    println(x, y, w);
+    0
+}
+"#;
+    let Some(output) = run_compiled("shadow", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["3 5 3"]);
+}
+
+#[test]
+fn shadowing_lets_get_their_own_cpp_variable() {
+    let source = r#"
+fn main() -> int {
+    let x = 1;
+    let x = x + 1;
+    {
+        let x = x * 10;
+        println(x);
+    }
+    for i in 0..2 {
+        let i = i + 100;
+        let x = x + i;
+        print(i, x, "");
+    }
+    println(x);
+    0
+}
+"#;
+    let Some(output) = run_compiled("shadowing", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["20", "100 102 101 103 2"]);
+}
+
+#[test]
+fn names_from_the_c_and_cpp_libraries_compile() {
+    let source = r#"
+struct stdin { errno: int, value: int }
+enum signal { time(int), getenv }
+fn time(new: int) -> int { new * 2 }
+fn main() -> int {
+    let getenv = signal::time(time(2));
+    let x = 1;
+    let x = x + 3;
+    match getenv { signal::time(int) => println(int), signal::getenv => println(0) }
+    println(stdin { errno: x, value: 1 });
+    0
+}
+"#;
+    let Some(output) = run_compiled("names", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["4", "stdin { errno: 4, value: 1 }"]);
+}
+
+#[test]
+fn variants_may_be_named_like_the_generated_member() {
+    let source = r#"
+enum E { value(int), other }
+fn main() -> int {
+    let e = E::value(3);
+    match e { E::value(n) => println(n), E::other => println(0) }
+    println(e);
+    0
+}
+"#;
+    let Some(output) = run_compiled("value", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["3", "value(3)"]);
+}
+
+#[test]
+fn locals_may_shadow_top_level_variables() {
+    let source = r#"
+let s = "global";
+let n = 2;
+fn f(n: int) -> string {
+    let s = s;
+    println(s, n);
+    s
+}
+fn g() {
+    let n = n + 1;
+    println(n);
+}
+println(f(5));
+g();
+"#;
+    let Some(output) = run_compiled("global", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["global 5", "global", "3"]);
+}
+
+#[test]
+fn top_level_lets_are_initialized_before_main() {
+    let source = r#"
+let base = 10;
+let scaled = base * 3;
+fn total(n: int) -> int { n + scaled }
+fn main() -> int {
+    println(base, total(2));
+    0
+}
+"#;
+    let Some(output) = run_compiled("globals", source) else {
+        return;
+    };
+
+    assert_eq!(lines(&output), vec!["10 32"]);
+}
+
+#[test]
+fn main_returns_the_exit_status() {
+    let Some(output) = run_program("status", "fn main() -> int { 3 }") else {
+        return;
+    };
+    assert_eq!(output.status.code(), Some(3));
+
+    let source = r#"
+fn main() {
+    println("done");
+}
+"#;
+    let Some(output) = run_program("void", source) else {
+        return;
+    };
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        lines(&String::from_utf8_lossy(&output.stdout)),
+        vec!["done"]
+    );
+}
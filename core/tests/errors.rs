@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use log::Level;
+use pulse_core::build::BuildProcess;
+use pulse_core::error::explanations::explain;
+
+#[test]
+fn every_code_has_an_explanation() {
+    let codes: Vec<String> = (1..)
+        .map(|number| format!("P{:04}", number))
+        .take_while(|code| explain(code).is_some())
+        .collect();
+
+    assert_eq!(codes.len(), 37);
+    assert!(explain("p0009").is_some());
+}
+
+#[test]
+fn errors_carry_their_code() {
+    let source = "fn main() {\n    let x: int = true;\n}\n";
+
+    let error = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"))
+        .analyze()
+        .unwrap_err();
+    assert_eq!(error.code(), None);
+
+    let codes: Vec<Option<&str>> = error
+        .into_diagnostics()
+        .into_iter()
+        .map(|diagnostic| diagnostic.code)
+        .collect();
+    assert_eq!(codes, vec![Some("P0009")]);
+}
+
+#[test]
+fn warnings_have_no_code_even_when_denied() {
+    let source = "fn main() {\n    let unused = 1;\n}\n";
+
+    let mut build = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"));
+    let warnings = build.analyze().expect("Warnings should not fail the build");
+    assert!(!warnings.is_empty());
+
+    let mut build = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"));
+    build.deny_warnings = true;
+    let denied = build.analyze().unwrap_err().into_diagnostics();
+    assert_eq!(denied.len(), 1);
+    assert_eq!(denied[0].code, None);
+    assert_eq!(denied[0].level, Level::Error);
+}
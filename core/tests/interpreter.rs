@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use pulse_core::build::BuildProcess;
+
+/// Exit status of `source` run by the interpreter with `args`, or the code of the error it
+/// stopped with.
+fn run(source: &str, args: &[&str]) -> Result<i32, Option<&'static str>> {
+    let mut build = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"));
+    build.analyze().expect("Source should type check");
+    build
+        .interpret(args.iter().map(|arg| arg.to_string()).collect())
+        .map_err(|error| error.code())
+}
+
+/// Exit status of `source` run by the interpreter, the value its `main` returns.
+fn status(source: &str) -> i32 {
+    run(source, &[]).expect("Program should run")
+}
+
+#[test]
+fn main_returns_the_exit_status() {
+    assert_eq!(status("fn main() -> int { 7 }"), 7);
+    assert_eq!(status("fn main() { let x = 7; }"), 0);
+    assert_eq!(status("let x = 7;\nprintln(x);"), 0);
+}
+
+#[test]
+fn main_receives_the_arguments_after_the_program_name() {
+    let source = r#"
+fn main(args: [string]) -> int {
+    std::array::len(args)
+}
+"#;
+
+    assert_eq!(run(source, &["a", "bcd"]).ok(), Some(2));
+}
+
+#[test]
+fn continue_inside_an_operand_skips_the_iteration() {
+    let source = r#"
+fn main() -> int {
+    let sum = 0;
+    for i in 0..5 {
+        sum = sum + 1 + { if i == 1 { continue; } i };
+    }
+    sum
+}
+"#;
+
+    assert_eq!(status(source), 13);
+}
+
+#[test]
+fn break_inside_an_argument_leaves_the_loop() {
+    let source = r#"
+fn add(a: int, b: int) -> int { a + b }
+fn main() -> int {
+    let sum = 0;
+    for i in 0..5 {
+        sum = add(sum, { if i == 3 { break; } i });
+    }
+    sum
+}
+"#;
+
+    assert_eq!(status(source), 3);
+}
+
+#[test]
+fn return_inside_a_while_condition_leaves_the_function() {
+    let source = r#"
+fn count(n: int) -> int {
+    while n > { if n == 9 { return 90; } 0 } {
+        n = n - 1;
+    }
+    n
+}
+fn main() -> int {
+    count(3) + count(9)
+}
+"#;
+
+    assert_eq!(status(source), 90);
+}
+
+#[test]
+fn steps_that_are_not_positive_are_runtime_errors() {
+    let source = r#"
+fn main() {
+    let step = 0;
+    for i in 0..3 step step {}
+}
+"#;
+
+    assert_eq!(run(source, &[]), Err(Some("P0018")));
+}
+
+#[test]
+fn indices_of_an_assigned_place_are_evaluated_once() {
+    let source = r#"
+struct P { x: int }
+fn main() -> int {
+    let order = [0, 1];
+    let ps = [P { x: 1 }, P { x: 2 }];
+    ps[std::array::pop(order)].x = 5;
+    let rows = [1];
+    let grid = [[1], [2]];
+    std::array::push(grid[std::array::pop(rows)], 3);
+    std::array::len(order) * 1000 + ps[0].x * 100 + ps[1].x * 10 + std::array::len(grid[1])
+}
+"#;
+
+    assert_eq!(status(source), 1152);
+}
@@ -0,0 +1,114 @@
+use pulse_core::error::error::Error;
+use pulse_core::lexer::token::{Operator, TokenKind};
+use pulse_core::lexer::Lexer;
+
+fn kinds(input: &str) -> Vec<TokenKind> {
+    Lexer::new(input)
+        .tokenize()
+        .expect("Input should lex")
+        .into_iter()
+        .map(|token| token.kind)
+        .collect()
+}
+
+#[test]
+fn comments_accept_non_ascii_text() {
+    let input = "// café\n/* naïve /* ☃ */ */ 1";
+
+    assert_eq!(kinds(input), vec![TokenKind::Number(1), TokenKind::Eof]);
+}
+
+#[test]
+fn spans_after_non_ascii_text_hold_their_own_text() {
+    let tokens = Lexer::new("// é\nlet x").tokenize().unwrap();
+
+    let literals: Vec<&str> = tokens
+        .iter()
+        .map(|token| token.span.literal.as_str())
+        .collect();
+    assert_eq!(literals, vec!["let", "x", "\0"]);
+}
+
+#[test]
+fn comments_are_skipped_like_whitespace() {
+    let input = "1 // 2\n/* 3 /* 4 */ 5 */ 6 / 7";
+
+    assert_eq!(
+        kinds(input),
+        vec![
+            TokenKind::Number(1),
+            TokenKind::Number(6),
+            TokenKind::Operator(Operator::Slash),
+            TokenKind::Number(7),
+            TokenKind::Eof
+        ]
+    );
+}
+
+#[test]
+fn unterminated_block_comments_point_at_their_opening() {
+    let error = Lexer::new("1\n  /* a /* b */").tokenize().unwrap_err();
+
+    let Error::UnterminatedComment(span, _) = error else {
+        panic!("Expected an unterminated comment, got {:?}", error);
+    };
+    assert_eq!((span.start.line, span.start.column), (1, 2));
+    assert_eq!(span.literal, "/*");
+}
+
+#[test]
+fn integer_literals_must_fit_in_32_bits() {
+    assert_eq!(
+        kinds("2147483647"),
+        vec![TokenKind::Number(2147483647), TokenKind::Eof]
+    );
+
+    for input in ["2147483648", "99999999999999999999999"] {
+        let error = Lexer::new(input).tokenize().unwrap_err();
+        assert!(matches!(error, Error::IntegerOutOfRange(..)));
+        assert_eq!(error.code(), Some("P0022"));
+    }
+}
+
+#[test]
+fn string_literals_keep_non_ascii_characters() {
+    let input = r#"println("é", "na\u{ef}ve \u{1F600}");"#;
+
+    let strings: Vec<TokenKind> = kinds(input)
+        .into_iter()
+        .filter(|kind| matches!(kind, TokenKind::String(_)))
+        .collect();
+    assert_eq!(
+        strings,
+        vec![
+            TokenKind::String("é".to_string()),
+            TokenKind::String("naïve 😀".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn escapes_in_string_literals_are_decoded() {
+    let input = r#""a\nb\t\"c\"\\ \u{41}""#;
+
+    assert_eq!(
+        kinds(input),
+        vec![
+            TokenKind::String("a\nb\t\"c\"\\ A".to_string()),
+            TokenKind::Eof
+        ]
+    );
+}
+
+#[test]
+fn malformed_string_literals_are_errors() {
+    let error = Lexer::new(r#""a\q""#).tokenize().unwrap_err();
+    assert!(matches!(error, Error::InvalidEscape(..)), "{:?}", error);
+
+    let error = Lexer::new(r#""never closed"#).tokenize().unwrap_err();
+    assert!(
+        matches!(error, Error::UnterminatedString(..)),
+        "{:?}",
+        error
+    );
+}
@@ -0,0 +1,257 @@
+use pulse_core::repl::Repl;
+
+/// Value and type `input` evaluates to, as the REPL prints them.
+fn eval(repl: &mut Repl, input: &str) -> Option<String> {
+    repl.eval(input)
+        .expect("Entry should run")
+        .map(|(value, ty)| format!("{}: {}", value.repr(), ty))
+}
+
+#[test]
+fn functions_and_bindings_stay_visible_to_later_entries() {
+    let mut repl = Repl::new();
+
+    assert_eq!(
+        eval(&mut repl, "fn add(a: int, b: int) -> int { return a + b; }"),
+        None
+    );
+    assert_eq!(eval(&mut repl, "let x = add(1, 2);"), None);
+    assert_eq!(eval(&mut repl, "x * 2"), Some("6: int".to_string()));
+    assert_eq!(eval(&mut repl, "x = 4"), Some("4: int".to_string()));
+}
+
+#[test]
+fn a_failing_entry_leaves_no_definitions_behind() {
+    let mut repl = Repl::new();
+
+    assert!(repl.eval("let y = true; y + 1").is_err());
+    assert!(repl.eval("y").is_err());
+    assert_eq!(eval(&mut repl, "let y = 1;"), None);
+}
+
+#[test]
+fn integers_wrap_around_at_32_bits() {
+    let mut repl = Repl::new();
+
+    assert_eq!(
+        eval(&mut repl, "2147483647 + 1"),
+        Some("-2147483648: int".to_string())
+    );
+    assert_eq!(eval(&mut repl, "65536 * 65536"), Some("0: int".to_string()));
+}
+
+#[test]
+fn integer_division_matches_the_generated_cpp() {
+    let mut repl = Repl::new();
+
+    assert_eq!(eval(&mut repl, "let one = 0 - 1;"), None);
+    assert_eq!(eval(&mut repl, "7 / one / 2"), Some("-3: int".to_string()));
+    assert_eq!(eval(&mut repl, "0 - 7 % 3"), Some("-1: int".to_string()));
+    assert_eq!(eval(&mut repl, "let min = 0 - 2147483647 - 1;"), None);
+    assert_eq!(
+        eval(&mut repl, "min / one"),
+        Some("-2147483648: int".to_string())
+    );
+    assert!(repl.eval("1 % 0").is_err());
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    let mut repl = Repl::new();
+
+    assert_eq!(
+        eval(&mut repl, "false && 1 / 0 == 0"),
+        Some("false: bool".to_string())
+    );
+    assert_eq!(
+        eval(&mut repl, "true || 1 / 0 == 0"),
+        Some("true: bool".to_string())
+    );
+    assert_eq!(
+        eval(&mut repl, "1 < 3 - 1 && !false"),
+        Some("true: bool".to_string())
+    );
+    assert!(repl.eval("1 && true").is_err());
+}
+
+#[test]
+fn for_loops_break_and_continue() {
+    let mut repl = Repl::new();
+
+    let source = r#"
+fn sum(n: int) -> int {
+    let sum = 0;
+    for i in 0..=n step 2 {
+        if i == 2 {
+            continue;
+        }
+        if i > 6 {
+            break;
+        }
+        sum = sum + i;
+    }
+    return sum;
+}"#;
+    assert_eq!(eval(&mut repl, source), None);
+    assert_eq!(eval(&mut repl, "sum(100)"), Some("10: int".to_string()));
+    assert_eq!(eval(&mut repl, "sum(5)"), Some("4: int".to_string()));
+}
+
+#[test]
+fn structs_are_values() {
+    let mut repl = Repl::new();
+
+    assert_eq!(eval(&mut repl, "struct Point { x: int, y: int }"), None);
+    assert_eq!(eval(&mut repl, "let a = Point { y: 2, x: 1 };"), None);
+    assert_eq!(eval(&mut repl, "let b = a;"), None);
+    assert_eq!(eval(&mut repl, "b.x = 5"), Some("5: int".to_string()));
+    assert_eq!(
+        eval(&mut repl, "a"),
+        Some("Point { x: 1, y: 2 }: Point".to_string())
+    );
+    assert_eq!(eval(&mut repl, "b.x + b.y"), Some("7: int".to_string()));
+}
+
+#[test]
+fn match_picks_the_first_arm_that_fits() {
+    let mut repl = Repl::new();
+
+    assert_eq!(eval(&mut repl, "enum Size { Small, Large(int) }"), None);
+    assert_eq!(eval(&mut repl, "let size = Size::Large(7);"), None);
+    assert_eq!(
+        eval(
+            &mut repl,
+            "match size { Size::Small => 0, Size::Large(n) => n * 2 }"
+        ),
+        Some("14: int".to_string())
+    );
+    assert_eq!(
+        eval(
+            &mut repl,
+            "match 3 { 1 => \"one\", 3 => \"three\", _ => \"many\" }"
+        ),
+        Some("\"three\": string".to_string())
+    );
+    assert_eq!(eval(&mut repl, "size"), Some("Large(7): Size".to_string()));
+}
+
+#[test]
+fn arrays_are_values() {
+    let mut repl = Repl::new();
+
+    assert_eq!(eval(&mut repl, "let a = [1, 2];"), None);
+    assert_eq!(eval(&mut repl, "let b = a;"), None);
+    assert_eq!(eval(&mut repl, "std::array::push(b, 3);"), None);
+    assert_eq!(eval(&mut repl, "a[0] = 5;"), Some("5: int".to_string()));
+    assert_eq!(eval(&mut repl, "a"), Some("[5, 2]: [int]".to_string()));
+    assert_eq!(eval(&mut repl, "b"), Some("[1, 2, 3]: [int]".to_string()));
+    assert_eq!(
+        eval(&mut repl, "std::array::len(b)"),
+        Some("3: int".to_string())
+    );
+}
+
+#[test]
+fn floats_print_like_the_generated_cpp() {
+    let mut repl = Repl::new();
+
+    assert_eq!(
+        eval(&mut repl, "1.0 / 3.0"),
+        Some("0.333333: float".to_string())
+    );
+    assert_eq!(
+        eval(&mut repl, "1234567.0"),
+        Some("1.23457e+06: float".to_string())
+    );
+    assert_eq!(
+        eval(&mut repl, "2.5e-5"),
+        Some("2.5e-05: float".to_string())
+    );
+    assert_eq!(eval(&mut repl, "7.9 as int"), Some("7: int".to_string()));
+    assert_eq!(
+        eval(&mut repl, "3 as float / 2.0"),
+        Some("1.5: float".to_string())
+    );
+}
+
+#[test]
+fn strings_are_shown_with_their_escapes() {
+    let mut repl = Repl::new();
+
+    assert_eq!(
+        eval(&mut repl, r#""tab\there \"quoted\"""#),
+        Some(r#""tab\there \"quoted\"": string"#.to_string())
+    );
+}
+
+#[test]
+fn unary_operators_match_the_generated_cpp() {
+    let mut repl = Repl::new();
+
+    assert_eq!(eval(&mut repl, "let a = 2;"), None);
+    assert_eq!(eval(&mut repl, "-(a + 3)"), Some("-5: int".to_string()));
+    assert_eq!(eval(&mut repl, "- -a"), Some("2: int".to_string()));
+    assert_eq!(eval(&mut repl, "~a"), Some("-3: int".to_string()));
+    assert_eq!(eval(&mut repl, "~-a"), Some("1: int".to_string()));
+    assert_eq!(eval(&mut repl, "-1.5"), Some("-1.5: float".to_string()));
+}
+
+#[test]
+fn operators_group_like_the_generated_cpp() {
+    let mut repl = Repl::new();
+
+    assert_eq!(eval(&mut repl, "let a = 6;"), None);
+    assert_eq!(eval(&mut repl, "a - (3 - 2)"), Some("5: int".to_string()));
+    assert_eq!(
+        eval(&mut repl, "a & 3 == 2"),
+        Some("true: bool".to_string())
+    );
+    assert_eq!(eval(&mut repl, "a & 3 + 1"), Some("4: int".to_string()));
+}
+
+#[test]
+fn powers_wrap_around_like_the_generated_cpp() {
+    let mut repl = Repl::new();
+
+    assert_eq!(
+        eval(&mut repl, "3 ** 21"),
+        Some("1870418611: int".to_string())
+    );
+    assert_eq!(eval(&mut repl, "-2 ** 2"), Some("4: int".to_string()));
+    assert_eq!(eval(&mut repl, "2 ** 32"), Some("0: int".to_string()));
+    assert_eq!(eval(&mut repl, "2 ** -1"), Some("0: int".to_string()));
+    assert_eq!(eval(&mut repl, "(-1) ** -3"), Some("-1: int".to_string()));
+}
+
+#[test]
+fn trailing_expressions_are_returned() {
+    let mut repl = Repl::new();
+
+    assert_eq!(
+        eval(
+            &mut repl,
+            "fn abs(n: int) -> int { if n < 0 { 0 - n } else { n } }"
+        ),
+        None
+    );
+    assert_eq!(
+        eval(&mut repl, "abs(0 - 3) + abs(4)"),
+        Some("7: int".to_string())
+    );
+}
+
+#[test]
+fn errors_found_before_a_parse_error_are_reported() {
+    let mut repl = Repl::new();
+
+    let error = repl.eval("fn f() {}\nfn f() {}\nlet = 2;").unwrap_err();
+    assert_eq!(error.into_diagnostics().len(), 2);
+}
+
+#[test]
+fn every_parse_error_of_an_entry_is_reported() {
+    let mut repl = Repl::new();
+
+    let error = repl.eval("let = 1;\nlet = 2;").unwrap_err();
+    assert_eq!(error.into_diagnostics().len(), 2);
+}
@@ -0,0 +1,415 @@
+use std::path::PathBuf;
+
+use pulse_core::build::BuildProcess;
+
+/// Codes of the errors found while checking `source`, in the order they are reported.
+fn error_codes(source: &str) -> Vec<&'static str> {
+    match BuildProcess::new(source.to_string(), PathBuf::from("main.pulse")).analyze() {
+        Ok(_) => vec![],
+        Err(error) => error
+            .into_diagnostics()
+            .into_iter()
+            .filter_map(|diagnostic| diagnostic.code)
+            .collect(),
+    }
+}
+
+#[test]
+fn loop_control_is_only_allowed_inside_loops() {
+    let source = r#"
+fn main() {
+    if true {
+        break;
+    }
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0023"]);
+}
+
+#[test]
+fn loop_control_is_not_allowed_in_a_while_condition() {
+    let source = r#"
+fn main() {
+    for i in 0..3 {
+        while { if i == 1 { continue; } i < 0 } {}
+    }
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0023"]);
+}
+
+#[test]
+fn bounds_of_a_range_must_be_ints() {
+    let source = r#"
+fn main() {
+    for i in 0..true {}
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0009"]);
+}
+
+#[test]
+fn struct_literals_name_every_field_once() {
+    let unknown = r#"
+struct Point { x: int, y: int }
+fn main() {
+    let p = Point { x: 1, y: 2, z: 3 };
+}
+"#;
+    let missing = r#"
+struct Point { x: int, y: int }
+fn main() {
+    let p = Point { x: 1 };
+}
+"#;
+    let mismatched = r#"
+struct Point { x: int, y: int }
+fn main() {
+    let p = Point { x: 1, y: true };
+}
+"#;
+
+    assert_eq!(error_codes(unknown), vec!["P0025"]);
+    assert_eq!(error_codes(missing), vec!["P0026"]);
+    assert_eq!(error_codes(mismatched), vec!["P0009"]);
+}
+
+#[test]
+fn structs_cannot_contain_themselves() {
+    let source = r#"
+struct Node { value: int, next: Node }
+fn main() {}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0028"]);
+}
+
+#[test]
+fn only_variables_and_their_fields_can_be_assigned() {
+    let source = r#"
+struct Point { x: int, y: int }
+fn origin() -> Point {
+    return Point { x: 0, y: 0 };
+}
+fn main() {
+    origin().x = 1;
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0027"]);
+}
+
+#[test]
+fn paths_naming_no_enum_are_not_found() {
+    let source = r#"
+enum Shape { Circle }
+fn main() {
+    println(Nope::Circle);
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0010"]);
+}
+
+#[test]
+fn unknown_variants_of_an_enum_are_reported() {
+    let source = r#"
+enum Shape { Circle }
+fn main() {
+    println(Shape::Nope);
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0029"]);
+}
+
+#[test]
+fn matches_cover_every_variant() {
+    let source = r#"
+enum Shape { Circle(int), Square(int), Empty }
+fn area(shape: Shape) -> int {
+    return match shape {
+        Shape::Circle(r) => 3 * r * r,
+        Shape::Square(side) => side * side,
+    };
+}
+fn main() {}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0030"]);
+}
+
+#[test]
+fn let_with_an_unknown_path_is_not_found() {
+    for initializer in ["foo::bar()", "std::io", "Nope::Circle"] {
+        let source = format!("enum Shape {{ Circle }}\nfn main() {{ let a = {initializer}; }}");
+
+        assert_eq!(error_codes(&source), vec!["P0010"], "{initializer}");
+    }
+}
+
+#[test]
+fn let_with_an_empty_array_cannot_infer_its_type() {
+    let source = r#"
+fn main() {
+    let a = [];
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0033"]);
+}
+
+#[test]
+fn ints_and_floats_do_not_mix_without_a_cast() {
+    let source = r#"
+fn main() {
+    let x = 1 + 2.0;
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0009"]);
+}
+
+#[test]
+fn only_numbers_can_be_cast() {
+    let source = r#"
+fn main() {
+    let x = true as int;
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0032"]);
+}
+
+#[test]
+fn bodies_without_a_trailing_value_must_return() {
+    let source = r#"
+fn f(n: int) -> int {
+    if n > 0 {
+        return 1;
+    }
+}
+fn main() {}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0031"]);
+}
+
+#[test]
+fn top_level_calls_cannot_use_variables_initialized_later() {
+    let source = r#"
+let b = twice();
+let a = 5;
+fn twice() -> int { value() * 2 }
+fn value() -> int { a }
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0036"]);
+
+    let source = r#"
+let a = 5;
+let b = twice();
+fn twice() -> int { a * 2 }
+println(b);
+"#;
+
+    assert!(error_codes(source).is_empty());
+}
+
+#[test]
+fn variables_of_the_then_branch_are_not_visible_in_else() {
+    let source = r#"
+fn main() {
+    let c = true;
+    if c { let z = 1; } else { println(z); }
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0010"]);
+}
+
+#[test]
+fn scripts_cannot_define_main() {
+    let source = r#"
+let greeting = "hi";
+println(greeting);
+fn main() {}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0035"]);
+}
+
+#[test]
+fn main_takes_nothing_or_the_arguments() {
+    for source in [
+        "fn main(n: int) {}",
+        "fn main(args: [string]) -> string { \"\" }",
+    ] {
+        assert_eq!(error_codes(source), vec!["P0007"]);
+    }
+    assert!(error_codes("fn main(args: [string]) -> int { 0 }").is_empty());
+}
+
+#[test]
+fn programs_without_statements_need_main() {
+    assert_eq!(error_codes("fn helper() {}"), vec!["P0008"]);
+}
+
+#[test]
+fn every_error_is_reported_in_source_order() {
+    let source = r#"
+fn f() -> int {
+    let b = nope;
+    break;
+}
+fn main() {
+    let a = 1 + true;
+    f(1);
+}
+"#;
+
+    assert_eq!(
+        error_codes(source),
+        vec!["P0010", "P0023", "P0031", "P0009", "P0013"]
+    );
+}
+
+#[test]
+fn parsing_continues_after_a_syntax_error() {
+    let source = r#"
+fn main() {
+    let = 1;
+    let b = 2 +;
+    let c: int = true;
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0004", "P0004", "P0009"]);
+}
+
+#[test]
+fn a_source_ending_inside_an_expression_is_a_syntax_error() {
+    for source in [
+        "fn main() { let x = ",
+        "fn main() { let x = (1 +",
+        "let x = -",
+    ] {
+        assert_eq!(error_codes(source).len(), 1, "{source}");
+    }
+}
+
+#[test]
+fn warnings_are_returned_to_the_caller() {
+    let source = r#"
+fn main() {
+    let unused = 1;
+}
+"#;
+
+    let mut build = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"));
+    let warnings = build.analyze().expect("Warnings should not fail the build");
+    assert!(!warnings.is_empty());
+
+    let mut build = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"));
+    build.deny_warnings = true;
+    assert!(build.analyze().is_err());
+}
+
+#[test]
+fn unused_and_dead_code_is_warned_about() {
+    let source = r#"
+fn helper() {}
+fn main() -> int {
+    let unused = 1;
+    let _ignored = 2;
+    while false {}
+    return 0;
+    println("never");
+}
+"#;
+
+    let mut build = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"));
+    build.deny_warnings = true;
+    let titles: Vec<String> = build
+        .analyze()
+        .unwrap_err()
+        .into_diagnostics()
+        .into_iter()
+        .map(|diagnostic| diagnostic.title)
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Function helper is never called",
+            "Unused variable unused",
+            "Loop never runs",
+            "Unreachable statement",
+        ]
+    );
+}
+
+#[test]
+fn std_call_without_a_module_is_undeclared() {
+    let source = r#"
+fn main() {
+    std::foo();
+}
+"#;
+
+    assert_eq!(error_codes(source), vec!["P0012"]);
+}
+
+#[test]
+fn one_character_names_are_not_suggested_for_each_other() {
+    let source = r#"
+fn main() {
+    let x = 1;
+    let count = 2;
+    println(x, count, y, conut);
+}
+"#;
+
+    let error = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"))
+        .analyze()
+        .unwrap_err();
+    let hints: Vec<Option<String>> = error
+        .into_diagnostics()
+        .into_iter()
+        .map(|diagnostic| diagnostic.hint)
+        .collect();
+    assert_eq!(hints, vec![None, Some("Did you mean `count`?".to_string())]);
+}
+
+#[test]
+fn unknown_names_get_a_suggestion() {
+    let source = r#"
+enum Shape { Circle }
+fn square(n: int) -> int { n * n }
+fn main() {
+    let total = 1;
+    println(totl, sqare(2), Shap::Circle);
+    std::io::prinltn(1);
+}
+"#;
+
+    let error = BuildProcess::new(source.to_string(), PathBuf::from("main.pulse"))
+        .analyze()
+        .unwrap_err();
+    let hints: Vec<Option<String>> = error
+        .into_diagnostics()
+        .into_iter()
+        .map(|diagnostic| diagnostic.hint)
+        .collect();
+    assert_eq!(
+        hints,
+        vec![
+            Some("Did you mean `total`?".to_string()),
+            Some("Did you mean `square`?".to_string()),
+            Some("Did you mean `Shape::Circle`?".to_string()),
+            Some("Did you mean `std::io::println`?".to_string()),
+        ]
+    );
+}